@@ -0,0 +1,53 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+use serde::Serialize;
+use serde_json::{to_value, Value};
+
+use crate::Validator;
+
+/// A validator declared on a field
+#[derive(Debug, Clone, PartialEq)]
+pub struct Constraint {
+    pub validator: Validator,
+    pub code: Cow<'static, str>,
+    pub message: Option<Cow<'static, str>>,
+    /// The params the errors of this validator have, apart from the `value`.
+    /// `length` and `range` have their bounds, `must_match`, the comparisons like `greater_than`
+    /// and the conditional `required_*` the name of the `other` field, with the `value` it's compared
+    /// to for `required_if` and `required_unless`, `contains` the `needle` and `regex` its `pattern`.
+    /// The presence rules like `exactly_one_of` have the names of their `fields`.
+    pub params: HashMap<Cow<'static, str>, Value>,
+    /// Whether the validator runs on each element of the field, with `each(...)`
+    pub each: bool,
+}
+
+impl Constraint {
+    pub fn new(
+        validator: Validator,
+        code: &'static str,
+        message: Option<Cow<'static, str>>,
+        each: bool,
+    ) -> Constraint {
+        Constraint { validator, code: Cow::from(code), message, params: HashMap::new(), each }
+    }
+
+    pub fn add_param<T: Serialize>(&mut self, name: Cow<'static, str>, val: &T) {
+        self.params.insert(name, to_value(val).unwrap());
+    }
+}
+
+/// The validators declared on a field, the field being named like in the errors.
+/// The struct level `schema` validations and presence rules are under `__all__`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldConstraints {
+    pub field: &'static str,
+    pub constraints: Vec<Constraint>,
+}
+
+/// Implemented by `#[derive(Validate)]` to describe the validators of a struct without
+/// a value, eg to document them or give hints to a client
+pub trait Constraints {
+    /// The validators of every field, in the order of declaration
+    fn constraints() -> Vec<FieldConstraints>;
+}
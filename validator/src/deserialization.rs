@@ -0,0 +1,627 @@
+//! Deserializing and validating a value in one step, with the deserialization failures
+//! turned into `ValidationErrors` so a caller only has one kind of error to deal with.
+//!
+//! The deserializer given is wrapped to keep track of the path of the value being deserialized.
+//! When it fails, the error is put at that path: `{"items": [{"name": 1}]}` gives an error on the
+//! `name` field of the element 0 of `items`, the same way `validate()` would report it.
+//! An error on an element itself, eg `{"tags": [1, "a"]}` for a `Vec<u32>`, is under `__all__`
+//! in the errors of the element, like the errors of `each`.
+//! Only the field names known statically by the `Deserialize` impl of a struct can be used as keys:
+//! an error in the value of a map is reported on the map itself, with the key in the `key` param.
+//!
+//! The codes of the errors are:
+//!
+//! - `missing`: a required field is missing
+//! - `unknown`: an unknown field was found with `#[serde(deny_unknown_fields)]`, reported in `__all__`
+//! - `type`: a value has the wrong type or is invalid
+//! - `parse`: the input is not valid JSON, reported in `__all__` (`from_json` only)
+
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::fmt;
+
+use serde::de::{self, Deserialize, DeserializeSeed, Visitor};
+use serde_json::Value;
+
+use crate::traits::Validate;
+use crate::types::{ValidationError, ValidationErrors, ValidationErrorsKind};
+
+/// Deserializes a JSON string and validates the result
+pub fn from_json<'a, T>(json: &'a str) -> Result<T, ValidationErrors>
+where
+    T: Deserialize<'a> + Validate,
+{
+    let mut deserializer = serde_json::Deserializer::from_str(json);
+    let value: T = deserialize_tracked(&mut deserializer)
+        .and_then(|value| deserializer.end().map(|_| value).map_err(|e| (e, None)))
+        .map_err(|(e, failure)| json_error(e, failure))?;
+    value.validate().map(|_| value)
+}
+
+/// Deserializes a `serde_json::Value` and validates the result
+pub fn from_value<T>(value: Value) -> Result<T, ValidationErrors>
+where
+    T: de::DeserializeOwned + Validate,
+{
+    let value: T = deserialize_tracked(value).map_err(|(e, failure)| json_error(e, failure))?;
+    value.validate().map(|_| value)
+}
+
+/// Deserializes a value with any serde `Deserializer` and validates the result.
+/// As the format of the errors is unknown, all of them apart from the missing and
+/// unknown fields get the `type` code.
+pub fn from_deserializer<'de, D, T>(deserializer: D) -> Result<T, ValidationErrors>
+where
+    D: de::Deserializer<'de>,
+    T: Deserialize<'de> + Validate,
+{
+    let value: T = deserialize_tracked(deserializer)
+        .map_err(|(e, failure)| to_validation_errors(failure, &e.to_string(), None))?;
+    value.validate().map(|_| value)
+}
+
+fn json_error(e: serde_json::Error, failure: Option<Failure>) -> ValidationErrors {
+    let mut message = e.to_string();
+    let position = if e.line() > 0 {
+        let suffix = format!(" at line {} column {}", e.line(), e.column());
+        if message.ends_with(&suffix) {
+            message.truncate(message.len() - suffix.len());
+        }
+        Some((e.line(), e.column()))
+    } else {
+        None
+    };
+
+    if e.is_data() {
+        return to_validation_errors(failure, &message, position);
+    }
+
+    let mut error = ValidationError::new("parse");
+    error.message = Some(Cow::from(message));
+    add_position(&mut error, position);
+    let mut errors = ValidationErrors::new();
+    errors.add("__all__", error);
+    errors
+}
+
+fn add_position(error: &mut ValidationError, position: Option<(usize, usize)>) {
+    if let Some((line, column)) = position {
+        error.add_param(Cow::from("line"), &line);
+        error.add_param(Cow::from("column"), &column);
+    }
+}
+
+/// Returns the field name between backticks at the start of a serde error message,
+/// eg `name` for "missing field `name`"
+fn find_quoted_field<'m>(message: &'m str, prefix: &str) -> Option<&'m str> {
+    if !message.starts_with(prefix) {
+        return None;
+    }
+    let rest = &message[prefix.len()..];
+    let end = rest.find('`')?;
+    Some(&rest[..end])
+}
+
+fn to_validation_errors(
+    failure: Option<Failure>,
+    message: &str,
+    position: Option<(usize, usize)>,
+) -> ValidationErrors {
+    let Failure { mut path, fields } = failure.unwrap_or_default();
+
+    // Only the static part of the path can be used as keys
+    let mut dynamic_key = None;
+    if let Some(index) = path.iter().position(|s| matches!(s, Segment::Key(_))) {
+        if let Segment::Key(ref key) = path[index] {
+            dynamic_key = Some(key.clone());
+        }
+        path.truncate(index);
+    }
+
+    let mut error = if let Some(field) = find_quoted_field(message, "missing field `") {
+        let mut error = ValidationError::new("missing");
+        match fields.iter().find(|f| **f == field) {
+            Some(field) if dynamic_key.is_none() => path.push(Segment::Field(field)),
+            _ => error.add_param(Cow::from("field"), &field),
+        }
+        error
+    } else if let Some(field) = find_quoted_field(message, "unknown field `") {
+        let mut error = ValidationError::new("unknown");
+        error.add_param(Cow::from("field"), &field);
+        error
+    } else {
+        ValidationError::new("type")
+    };
+    error.message = Some(Cow::from(message.to_string()));
+    if let Some(key) = dynamic_key {
+        error.add_param(Cow::from("key"), &key);
+    }
+    add_position(&mut error, position);
+
+    errors_at(&path, error)
+}
+
+/// Builds the errors for an error at the given path, starting from a struct
+fn errors_at(path: &[Segment], error: ValidationError) -> ValidationErrors {
+    let mut errors = ValidationErrors::new();
+    match path.split_first() {
+        Some((&Segment::Field(field), rest)) => add_at(&mut errors, field, rest, error),
+        _ => errors.add("__all__", error),
+    }
+    errors
+}
+
+/// Adds an error at the given path inside of `field`, the same way nested and `each`
+/// validations report them
+fn add_at(
+    errors: &mut ValidationErrors,
+    field: &'static str,
+    path: &[Segment],
+    error: ValidationError,
+) {
+    match path.split_first() {
+        None => errors.add(field, error),
+        Some((&Segment::Index(index), rest)) => {
+            let element = if let Some(Segment::Field(_)) = rest.first() {
+                errors_at(rest, error)
+            } else {
+                let mut element = ValidationErrors::new();
+                add_at(&mut element, "__all__", rest, error);
+                element
+            };
+            let list = vec![(index, Box::new(element))].into_iter().collect();
+            errors.add_nested(field, ValidationErrorsKind::List(list));
+        }
+        Some(_) => {
+            errors.add_nested(field, ValidationErrorsKind::Struct(Box::new(errors_at(path, error))))
+        }
+    }
+}
+
+/// A part of the path to a value
+#[derive(Debug, Clone)]
+enum Segment {
+    /// A field of a struct
+    Field(&'static str),
+    /// A key of a map, or a field not known by the struct
+    Key(String),
+    /// An element of a sequence
+    Index(usize),
+}
+
+/// The path to the value currently deserialized, built on the stack
+enum Chain<'a> {
+    Root,
+    Segment { parent: &'a Chain<'a>, segment: Segment },
+}
+
+impl<'a> Chain<'a> {
+    fn to_path(&self) -> Vec<Segment> {
+        let mut path = vec![];
+        let mut chain = self;
+        while let Chain::Segment { parent, ref segment } = *chain {
+            path.push(segment.clone());
+            chain = parent;
+        }
+        path.reverse();
+        path
+    }
+}
+
+/// Where the failure happened: the path and the fields of the struct at that path, if any
+#[derive(Debug, Default)]
+struct Failure {
+    path: Vec<Segment>,
+    fields: &'static [&'static str],
+}
+
+/// Records the innermost place an error happened, the first one to see it
+#[derive(Default)]
+struct Track {
+    failure: RefCell<Option<Failure>>,
+}
+
+impl Track {
+    fn trigger<E>(&self, chain: &Chain, fields: &'static [&'static str], error: E) -> E {
+        let mut failure = self.failure.borrow_mut();
+        if failure.is_none() {
+            *failure = Some(Failure { path: chain.to_path(), fields });
+        }
+        error
+    }
+}
+
+fn deserialize_tracked<'de, D, T>(deserializer: D) -> Result<T, (D::Error, Option<Failure>)>
+where
+    D: de::Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    let track = Track::default();
+    let result = T::deserialize(Wrap::new(deserializer, &Chain::Root, &track));
+    result.map_err(|e| (e, track.failure.into_inner()))
+}
+
+/// Wraps the deserializers, visitors and accesses of serde to track the path
+struct Wrap<'a, X> {
+    inner: X,
+    chain: &'a Chain<'a>,
+    track: &'a Track,
+    /// The fields of the struct being deserialized
+    fields: &'static [&'static str],
+    /// Where to store the key of a map, when deserializing one
+    key: Option<&'a RefCell<Option<String>>>,
+}
+
+impl<'a, X> Wrap<'a, X> {
+    fn new(inner: X, chain: &'a Chain<'a>, track: &'a Track) -> Self {
+        Wrap { inner, chain, track, fields: &[], key: None }
+    }
+
+    fn wrap<Y>(&self, inner: Y) -> Wrap<'a, Y> {
+        Wrap { inner, chain: self.chain, track: self.track, fields: self.fields, key: self.key }
+    }
+}
+
+macro_rules! forward_deserialize {
+    ($($method:ident($($arg:ident: $ty:ty),*))*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, $($arg: $ty,)* visitor: V) -> Result<V::Value, D::Error> {
+                let (chain, track) = (self.chain, self.track);
+                let visitor = self.wrap(visitor);
+                self.inner.$method($($arg,)* visitor).map_err(|e| track.trigger(chain, &[], e))
+            }
+        )*
+    };
+}
+
+impl<'a, 'de, D: de::Deserializer<'de>> de::Deserializer<'de> for Wrap<'a, D> {
+    type Error = D::Error;
+
+    forward_deserialize! {
+        deserialize_any()
+        deserialize_bool()
+        deserialize_i8()
+        deserialize_i16()
+        deserialize_i32()
+        deserialize_i64()
+        deserialize_i128()
+        deserialize_u8()
+        deserialize_u16()
+        deserialize_u32()
+        deserialize_u64()
+        deserialize_u128()
+        deserialize_f32()
+        deserialize_f64()
+        deserialize_char()
+        deserialize_str()
+        deserialize_string()
+        deserialize_bytes()
+        deserialize_byte_buf()
+        deserialize_option()
+        deserialize_unit()
+        deserialize_unit_struct(name: &'static str)
+        deserialize_newtype_struct(name: &'static str)
+        deserialize_seq()
+        deserialize_tuple(len: usize)
+        deserialize_tuple_struct(name: &'static str, len: usize)
+        deserialize_map()
+        deserialize_enum(name: &'static str, variants: &'static [&'static str])
+        deserialize_identifier()
+        deserialize_ignored_any()
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, D::Error> {
+        let (chain, track) = (self.chain, self.track);
+        let mut visitor = self.wrap(visitor);
+        visitor.fields = fields;
+        self.inner
+            .deserialize_struct(name, fields, visitor)
+            .map_err(|e| track.trigger(chain, fields, e))
+    }
+
+    fn is_human_readable(&self) -> bool {
+        self.inner.is_human_readable()
+    }
+}
+
+macro_rules! forward_visit {
+    ($($method:ident($ty:ty))*) => {
+        $(
+            fn $method<E: de::Error>(self, v: $ty) -> Result<V::Value, E> {
+                if let Some(key) = self.key {
+                    *key.borrow_mut() = Some(v.to_string());
+                }
+                self.inner.$method(v)
+            }
+        )*
+    };
+}
+
+impl<'a, 'de, V: Visitor<'de>> Visitor<'de> for Wrap<'a, V> {
+    type Value = V::Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        self.inner.expecting(formatter)
+    }
+
+    forward_visit! {
+        visit_bool(bool)
+        visit_i8(i8)
+        visit_i16(i16)
+        visit_i32(i32)
+        visit_i64(i64)
+        visit_i128(i128)
+        visit_u8(u8)
+        visit_u16(u16)
+        visit_u32(u32)
+        visit_u64(u64)
+        visit_u128(u128)
+        visit_f32(f32)
+        visit_f64(f64)
+        visit_char(char)
+        visit_str(&str)
+        visit_borrowed_str(&'de str)
+        visit_string(String)
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<V::Value, E> {
+        self.inner.visit_bytes(v)
+    }
+
+    fn visit_borrowed_bytes<E: de::Error>(self, v: &'de [u8]) -> Result<V::Value, E> {
+        self.inner.visit_borrowed_bytes(v)
+    }
+
+    fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<V::Value, E> {
+        self.inner.visit_byte_buf(v)
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<V::Value, E> {
+        self.inner.visit_none()
+    }
+
+    fn visit_some<D: de::Deserializer<'de>>(self, deserializer: D) -> Result<V::Value, D::Error> {
+        let deserializer = Wrap::new(deserializer, self.chain, self.track);
+        self.inner.visit_some(deserializer)
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<V::Value, E> {
+        self.inner.visit_unit()
+    }
+
+    fn visit_newtype_struct<D: de::Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<V::Value, D::Error> {
+        let deserializer = Wrap::new(deserializer, self.chain, self.track);
+        self.inner.visit_newtype_struct(deserializer)
+    }
+
+    fn visit_seq<A: de::SeqAccess<'de>>(self, seq: A) -> Result<V::Value, A::Error> {
+        let (chain, track, fields) = (self.chain, self.track, self.fields);
+        let seq = SeqAccess { inner: seq, chain, track, index: 0 };
+        self.inner.visit_seq(seq).map_err(|e| track.trigger(chain, fields, e))
+    }
+
+    fn visit_map<A: de::MapAccess<'de>>(self, map: A) -> Result<V::Value, A::Error> {
+        let (chain, track, fields) = (self.chain, self.track, self.fields);
+        let map = MapAccess { inner: map, chain, track, fields, key: RefCell::new(None) };
+        self.inner.visit_map(map).map_err(|e| track.trigger(chain, fields, e))
+    }
+
+    fn visit_enum<A: de::EnumAccess<'de>>(self, data: A) -> Result<V::Value, A::Error> {
+        // The content of the enums isn't tracked, their errors are reported on the enum itself
+        self.inner.visit_enum(data)
+    }
+}
+
+impl<'a, 'de, S: DeserializeSeed<'de>> DeserializeSeed<'de> for Wrap<'a, S> {
+    type Value = S::Value;
+
+    fn deserialize<D: de::Deserializer<'de>>(self, deserializer: D) -> Result<S::Value, D::Error> {
+        let deserializer = self.wrap(deserializer);
+        self.inner.deserialize(deserializer)
+    }
+}
+
+struct SeqAccess<'a, A> {
+    inner: A,
+    chain: &'a Chain<'a>,
+    track: &'a Track,
+    index: usize,
+}
+
+impl<'a, 'de, A: de::SeqAccess<'de>> de::SeqAccess<'de> for SeqAccess<'a, A> {
+    type Error = A::Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, A::Error> {
+        let chain = Chain::Segment { parent: self.chain, segment: Segment::Index(self.index) };
+        self.index += 1;
+        let track = self.track;
+        self.inner
+            .next_element_seed(Wrap::new(seed, &chain, track))
+            .map_err(|e| track.trigger(&chain, &[], e))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.inner.size_hint()
+    }
+}
+
+struct MapAccess<'a, A> {
+    inner: A,
+    chain: &'a Chain<'a>,
+    track: &'a Track,
+    fields: &'static [&'static str],
+    key: RefCell<Option<String>>,
+}
+
+impl<'a, 'de, A: de::MapAccess<'de>> de::MapAccess<'de> for MapAccess<'a, A> {
+    type Error = A::Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, A::Error> {
+        *self.key.borrow_mut() = None;
+        let (chain, track, fields) = (self.chain, self.track, self.fields);
+        let mut seed = Wrap::new(seed, chain, track);
+        seed.key = Some(&self.key);
+        self.inner.next_key_seed(seed).map_err(|e| track.trigger(chain, fields, e))
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, A::Error> {
+        let key = self.key.borrow_mut().take().unwrap_or_default();
+        let segment = match self.fields.iter().find(|field| **field == key) {
+            Some(field) => Segment::Field(field),
+            None => Segment::Key(key),
+        };
+        let chain = Chain::Segment { parent: self.chain, segment };
+        let track = self.track;
+        self.inner
+            .next_value_seed(Wrap::new(seed, &chain, track))
+            .map_err(|e| track.trigger(&chain, &[], e))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.inner.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use serde_derive::Deserialize;
+    use serde_json::json;
+
+    use super::{from_json, from_value};
+    use crate::{Validate, ValidationErrors, ValidationErrorsKind};
+
+    #[derive(Debug, Deserialize)]
+    struct Item {
+        name: String,
+        #[serde(default)]
+        tags: Vec<u32>,
+    }
+
+    impl Validate for Item {
+        fn validate(&self) -> Result<(), ValidationErrors> {
+            Ok(())
+        }
+    }
+
+    #[derive(Debug, Deserialize)]
+    #[serde(deny_unknown_fields)]
+    struct Order {
+        id: u32,
+        #[serde(default)]
+        items: Vec<Item>,
+        #[serde(default)]
+        prices: HashMap<String, f64>,
+    }
+
+    impl Validate for Order {
+        fn validate(&self) -> Result<(), ValidationErrors> {
+            let mut errors = ValidationErrors::new();
+            if self.id == 0 {
+                errors.add("id", crate::ValidationError::new("range"));
+            }
+            if errors.is_empty() {
+                Ok(())
+            } else {
+                Err(errors)
+            }
+        }
+    }
+
+    #[test]
+    fn test_from_json_ok() {
+        let order: Order =
+            from_json(r#"{"id": 1, "items": [{"name": "a", "tags": [1]}], "prices": {"a": 1.5}}"#)
+                .unwrap();
+        assert_eq!(order.id, 1);
+        assert_eq!(order.items[0].name, "a");
+        assert_eq!(order.items[0].tags, vec![1]);
+        assert_eq!(order.prices["a"], 1.5);
+    }
+
+    #[test]
+    fn test_from_json_validates() {
+        let errs = from_json::<Order>(r#"{"id": 0}"#).unwrap_err();
+        assert_eq!(errs.field_errors()["id"][0].code, "range");
+    }
+
+    #[test]
+    fn test_from_json_type_error() {
+        let errs = from_json::<Order>(r#"{"id": "1"}"#).unwrap_err();
+        let err = &errs.field_errors()["id"][0];
+        assert_eq!(err.code, "type");
+        assert_eq!(err.message.as_ref().unwrap(), "invalid type: string \"1\", expected u32");
+        assert_eq!(err.params["line"], 1);
+        assert_eq!(err.params["column"], 10);
+    }
+
+    #[test]
+    fn test_from_json_missing_field() {
+        let errs = from_json::<Order>(r#"{}"#).unwrap_err();
+        assert_eq!(errs.field_errors()["id"][0].code, "missing");
+    }
+
+    #[test]
+    fn test_from_json_unknown_field() {
+        let errs = from_json::<Order>(r#"{"id": 1, "other": 1}"#).unwrap_err();
+        let err = &errs.field_errors()["__all__"][0];
+        assert_eq!(err.code, "unknown");
+        assert_eq!(err.params["field"], "other");
+    }
+
+    #[test]
+    fn test_from_json_parse_error() {
+        let errs = from_json::<Order>(r#"{"id": 1"#).unwrap_err();
+        assert_eq!(errs.field_errors()["__all__"][0].code, "parse");
+
+        let errs = from_json::<Order>(r#"{"id": 1} 1"#).unwrap_err();
+        assert_eq!(errs.field_errors()["__all__"][0].code, "parse");
+    }
+
+    #[test]
+    fn test_from_value_nested_errors() {
+        let errs = from_value::<Order>(json!({"id": 1, "items": [{"name": "a"}, {}]})).unwrap_err();
+        if let ValidationErrorsKind::List(ref errs) = errs.errors()["items"] {
+            let err = &errs[&1].field_errors()["name"][0];
+            assert_eq!(err.code, "missing");
+            assert!(!err.params.contains_key("line"));
+        } else {
+            panic!("Expected list validation errors");
+        }
+
+        let errs = from_value::<Order>(json!({"id": 1, "items": [{"name": "a", "tags": [1, -1]}]}))
+            .unwrap_err();
+        if let ValidationErrorsKind::List(ref errs) = errs.errors()["items"] {
+            if let ValidationErrorsKind::List(ref errs) = errs[&0].errors()["tags"] {
+                assert_eq!(errs[&1].field_errors()["__all__"][0].code, "type");
+            } else {
+                panic!("Expected list validation errors");
+            }
+        } else {
+            panic!("Expected list validation errors");
+        }
+    }
+
+    #[test]
+    fn test_from_value_map_error() {
+        let errs = from_value::<Order>(json!({"id": 1, "prices": {"a": "b"}})).unwrap_err();
+        let err = &errs.field_errors()["prices"][0];
+        assert_eq!(err.code, "type");
+        assert_eq!(err.params["key"], "a");
+    }
+}
@@ -0,0 +1,309 @@
+//! Converters from `ValidationErrors` to the error formats of HTTP APIs: RFC 7807 problem details
+//! and JSON:API error objects.
+//!
+//! Both locate the errors with a JSON pointer (RFC 6901) built from the keys of the errors,
+//! eg `/items/0/name`. The errors in `__all__` point to the struct or the element they are about,
+//! eg `/tags/1` for `#[validate(each(email))] tags: Vec<String>`.
+
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use serde_derive::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::types::{ValidationError, ValidationErrors, ValidationErrorsKind};
+
+/// The content type of RFC 7807 problem details bodies
+pub const PROBLEM_JSON: &str = "application/problem+json";
+
+/// The content type of JSON:API documents
+pub const JSON_API: &str = "application/vnd.api+json";
+
+/// Every error with the JSON pointer to the value it is about, sorted by pointer, the indices
+/// being compared as numbers so `/items/2` comes before `/items/10`
+pub fn json_pointers(errors: &ValidationErrors) -> Vec<(String, &ValidationError)> {
+    let mut pointers = vec![];
+    collect(errors, "", &mut pointers);
+    // Sorting is stable so the errors of a field stay in the order of the validators
+    pointers.sort_by(|a, b| compare_pointers(&a.0, &b.0));
+    pointers
+}
+
+/// Compares two pointers segment by segment, the numeric segments coming first and being
+/// compared as numbers
+fn compare_pointers(a: &str, b: &str) -> Ordering {
+    let is_number =
+        |segment: &str| !segment.is_empty() && segment.bytes().all(|b| b.is_ascii_digit());
+    let compare_segments = |a: &str, b: &str| match (is_number(a), is_number(b)) {
+        (true, true) => {
+            let (a_digits, b_digits) = (a.trim_start_matches('0'), b.trim_start_matches('0'));
+            a_digits.len().cmp(&b_digits.len()).then(a_digits.cmp(b_digits)).then(a.cmp(b))
+        }
+        (true, false) => Ordering::Less,
+        (false, true) => Ordering::Greater,
+        (false, false) => a.cmp(b),
+    };
+
+    let (mut a, mut b) = (a.split('/'), b.split('/'));
+    loop {
+        match (a.next(), b.next()) {
+            (Some(a), Some(b)) => match compare_segments(a, b) {
+                Ordering::Equal => continue,
+                ordering => return ordering,
+            },
+            (a, b) => return a.is_some().cmp(&b.is_some()),
+        }
+    }
+}
+
+fn collect<'a>(
+    errors: &'a ValidationErrors,
+    pointer: &str,
+    pointers: &mut Vec<(String, &'a ValidationError)>,
+) {
+    for (field, kind) in errors.errors() {
+        let field_pointer = if field == "__all__" {
+            pointer.to_string()
+        } else {
+            format!("{}/{}", pointer, escape(field))
+        };
+        match kind {
+            ValidationErrorsKind::Field(errors) => {
+                pointers.extend(errors.iter().map(|error| (field_pointer.clone(), error)))
+            }
+            ValidationErrorsKind::Struct(errors) => collect(errors, &field_pointer, pointers),
+            ValidationErrorsKind::List(list) => {
+                for (index, errors) in list {
+                    collect(errors, &format!("{}/{}", field_pointer, index), pointers);
+                }
+            }
+        }
+    }
+}
+
+/// Escapes a key for a JSON pointer, `~` becoming `~0` and `/` becoming `~1`
+fn escape(key: &str) -> Cow<'_, str> {
+    if key.contains('~') || key.contains('/') {
+        Cow::from(key.replace('~', "~0").replace('/', "~1"))
+    } else {
+        Cow::from(key)
+    }
+}
+
+/// An RFC 7807 `application/problem+json` body, with the errors in the `errors` extension member
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProblemDetails {
+    #[serde(rename = "type")]
+    pub problem_type: String,
+    pub title: String,
+    pub status: u16,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instance: Option<String>,
+    pub errors: Vec<ProblemError>,
+}
+
+/// An error in the `errors` member of `ProblemDetails`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProblemError {
+    pub pointer: String,
+    pub code: Cow<'static, str>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<Cow<'static, str>>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub params: HashMap<Cow<'static, str>, Value>,
+}
+
+impl ProblemDetails {
+    /// A problem of type `about:blank` with the `422 Unprocessable Entity` status
+    pub fn new(errors: &ValidationErrors) -> ProblemDetails {
+        let errors = json_pointers(errors)
+            .into_iter()
+            .map(|(pointer, error)| ProblemError {
+                pointer,
+                code: error.code.clone(),
+                message: error.message.clone(),
+                params: error.params.clone(),
+            })
+            .collect();
+
+        ProblemDetails {
+            problem_type: "about:blank".to_string(),
+            title: "Unprocessable Entity".to_string(),
+            status: 422,
+            detail: None,
+            instance: None,
+            errors,
+        }
+    }
+
+    pub fn with_type(mut self, problem_type: impl Into<String>) -> ProblemDetails {
+        self.problem_type = problem_type.into();
+        self
+    }
+
+    pub fn with_title(mut self, title: impl Into<String>) -> ProblemDetails {
+        self.title = title.into();
+        self
+    }
+
+    pub fn with_status(mut self, status: u16) -> ProblemDetails {
+        self.status = status;
+        self
+    }
+
+    pub fn with_detail(mut self, detail: impl Into<String>) -> ProblemDetails {
+        self.detail = Some(detail.into());
+        self
+    }
+
+    pub fn with_instance(mut self, instance: impl Into<String>) -> ProblemDetails {
+        self.instance = Some(instance.into());
+        self
+    }
+}
+
+impl From<&ValidationErrors> for ProblemDetails {
+    fn from(errors: &ValidationErrors) -> ProblemDetails {
+        ProblemDetails::new(errors)
+    }
+}
+
+/// A JSON:API error object
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JsonApiError {
+    pub status: String,
+    pub code: Cow<'static, str>,
+    /// The message of the error
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<Cow<'static, str>>,
+    pub source: JsonApiSource,
+    /// The params of the error
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub meta: HashMap<Cow<'static, str>, Value>,
+}
+
+/// The `source` of a JSON:API error object
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JsonApiSource {
+    pub pointer: String,
+}
+
+/// The JSON:API error objects of the errors, with a `422` status. The pointers are prefixed
+/// with `pointer_prefix`, usually `/data/attributes` for the errors of the attributes of a resource.
+pub fn json_api_errors(errors: &ValidationErrors, pointer_prefix: &str) -> Vec<JsonApiError> {
+    json_pointers(errors)
+        .into_iter()
+        .map(|(pointer, error)| JsonApiError {
+            status: "422".to_string(),
+            code: error.code.clone(),
+            detail: error.message.clone(),
+            source: JsonApiSource { pointer: format!("{}{}", pointer_prefix, pointer) },
+            meta: error.params.clone(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use serde_json::json;
+
+    use super::{json_api_errors, json_pointers, ProblemDetails};
+    use crate::{ValidationError, ValidationErrors};
+
+    fn errors() -> ValidationErrors {
+        let mut name = ValidationError::new("length");
+        name.add_param(Cow::from("min"), &1);
+        let mut item = ValidationErrors::new();
+        item.add("name", name);
+        let items = ValidationErrors::merge(Ok(()), "items", Err(item));
+        let items = ValidationErrors::merge_all(Ok(()), "items", vec![Ok(()), items]);
+
+        let mut mail = ValidationError::new("email");
+        mail.message = Some(Cow::from("Invalid email"));
+        let mut address = ValidationErrors::new();
+        address.add("zip/code", ValidationError::new("length"));
+        address.add("__all__", ValidationError::new("address"));
+
+        let mut errors = items.unwrap_err();
+        errors.add("mail", mail);
+        ValidationErrors::merge(Err(errors), "address", Err(address)).unwrap_err()
+    }
+
+    #[test]
+    fn test_json_pointers() {
+        let pointers = json_pointers(&errors())
+            .into_iter()
+            .map(|(pointer, error)| (pointer, error.code.to_string()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            pointers,
+            vec![
+                ("/address".to_string(), "address".to_string()),
+                ("/address/zip~1code".to_string(), "length".to_string()),
+                ("/items/1/name".to_string(), "length".to_string()),
+                ("/mail".to_string(), "email".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_json_pointers_sort_indices_as_numbers() {
+        let mut item = ValidationErrors::new();
+        item.add("name", ValidationError::new("length"));
+        let item = ValidationErrors::merge(Ok(()), "items", Err(item));
+        let mut items = vec![Ok(()); 11];
+        items[2] = item.clone();
+        items[10] = item;
+        let errors = ValidationErrors::merge_all(Ok(()), "items", items).unwrap_err();
+
+        let pointers =
+            json_pointers(&errors).into_iter().map(|(pointer, _)| pointer).collect::<Vec<_>>();
+        assert_eq!(pointers, vec!["/items/2/name".to_string(), "/items/10/name".to_string()]);
+    }
+
+    #[test]
+    fn test_problem_details() {
+        let problem = ProblemDetails::new(&errors()).with_detail("The order is invalid");
+        let value = serde_json::to_value(&problem).unwrap();
+        assert_eq!(value["type"], "about:blank");
+        assert_eq!(value["status"], 422);
+        assert_eq!(value["detail"], "The order is invalid");
+        assert_eq!(
+            value["errors"][2],
+            json!({"pointer": "/items/1/name", "code": "length", "params": {"min": 1}})
+        );
+        assert_eq!(
+            value["errors"][3],
+            json!({"pointer": "/mail", "code": "email", "message": "Invalid email"})
+        );
+        assert_eq!(serde_json::from_value::<ProblemDetails>(value).unwrap(), problem);
+    }
+
+    #[test]
+    fn test_json_api_errors() {
+        let errors = serde_json::to_value(json_api_errors(&errors(), "/data/attributes")).unwrap();
+        assert_eq!(
+            errors[2],
+            json!({
+                "status": "422",
+                "code": "length",
+                "source": {"pointer": "/data/attributes/items/1/name"},
+                "meta": {"min": 1},
+            })
+        );
+        assert_eq!(
+            errors[3],
+            json!({
+                "status": "422",
+                "code": "email",
+                "detail": "Invalid email",
+                "source": {"pointer": "/data/attributes/mail"},
+            })
+        );
+    }
+}
@@ -0,0 +1,330 @@
+//! JSON Schema (draft 2020-12) generation from the validation constraints.
+//!
+//! With the `json_schema` feature, `#[derive(Validate)]` also implements `ToJsonSchema`,
+//! describing the fields and the constraints of their validators that JSON Schema can express:
+//! `length`, `range`, `email`, `url`, `regex`, `contains` and `required`, and on the struct
+//! `at_least_one_of`, `exactly_one_of` and `mutually_exclusive`.
+//! Nested structs are added to the `$defs` of the schema and referenced with `$ref`.
+
+use std::borrow::Cow;
+
+pub use serde_json::{Map, Value};
+
+use crate::keywords::{add_constraint, add_struct_constraint};
+use crate::{Constraint, FieldConstraints};
+
+/// The JSON Schema version of the generated schemas
+pub const SCHEMA_VERSION: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// A type that can describe itself with a JSON Schema
+pub trait ToJsonSchema {
+    /// The name of the schema in `$defs` when it is referenced by another one
+    fn schema_name() -> Cow<'static, str>;
+
+    /// The schema of the type, adding the schemas of the types it references to `defs`
+    fn schema(defs: &mut Map<String, Value>) -> Value;
+
+    /// A full JSON Schema document for the type
+    fn json_schema() -> Value
+    where
+        Self: Sized,
+    {
+        let mut defs = Map::new();
+        let mut schema = match Self::schema(&mut defs) {
+            Value::Object(schema) => schema,
+            other => return other,
+        };
+        let mut document = Map::new();
+        document.insert("$schema".to_string(), Value::from(SCHEMA_VERSION));
+        document.insert("title".to_string(), Value::from(Self::schema_name().into_owned()));
+        document.append(&mut schema);
+        if !defs.is_empty() {
+            document.insert("$defs".to_string(), Value::Object(defs));
+        }
+        Value::Object(document)
+    }
+}
+
+impl<T: ToJsonSchema + ?Sized> ToJsonSchema for &T {
+    fn schema_name() -> Cow<'static, str> {
+        T::schema_name()
+    }
+
+    fn schema(defs: &mut Map<String, Value>) -> Value {
+        T::schema(defs)
+    }
+}
+
+impl<T: ToJsonSchema + ?Sized> ToJsonSchema for Box<T> {
+    fn schema_name() -> Cow<'static, str> {
+        T::schema_name()
+    }
+
+    fn schema(defs: &mut Map<String, Value>) -> Value {
+        T::schema(defs)
+    }
+}
+
+/// The `$ref` to a schema of `$defs`, its name being escaped as a JSON pointer in a URI fragment
+fn definition_ref(name: &str) -> String {
+    let mut reference = "#/$defs/".to_string();
+    for c in name.chars() {
+        match c {
+            '~' => reference.push_str("~0"),
+            '/' => reference.push_str("~1"),
+            c if c.is_ascii_alphanumeric() || "-._!$&'()*+,;=:@".contains(c) => reference.push(c),
+            c => {
+                for byte in c.to_string().bytes() {
+                    reference.push_str(&format!("%{:02X}", byte));
+                }
+            }
+        }
+    }
+    reference
+}
+
+/// The schema of a property of an object, built by the derive
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PropertySchema(Map<String, Value>);
+
+impl PropertySchema {
+    /// A schema for values of the given JSON type, `None` if any type is accepted
+    pub fn new(json_type: Option<&str>) -> PropertySchema {
+        let mut schema = Map::new();
+        if let Some(json_type) = json_type {
+            schema.insert("type".to_string(), Value::from(json_type));
+        }
+        PropertySchema(schema)
+    }
+
+    /// A reference to the schema of `T`, which is added to `defs` if it isn't already there
+    pub fn reference<T: ToJsonSchema>(defs: &mut Map<String, Value>) -> PropertySchema {
+        let name = T::schema_name().into_owned();
+        if !defs.contains_key(&name) {
+            // Inserted before generating the schema in case `T` references itself
+            defs.insert(name.clone(), Value::Bool(true));
+            let schema = T::schema(defs);
+            defs.insert(name.clone(), schema);
+        }
+        let mut schema = Map::new();
+        schema.insert("$ref".to_string(), Value::from(definition_ref(&name)));
+        PropertySchema(schema)
+    }
+
+    fn json_type(&self) -> Option<&str> {
+        self.0.get("type").and_then(Value::as_str)
+    }
+
+    fn insert(mut self, key: &str, value: Value) -> PropertySchema {
+        self.0.insert(key.to_string(), value);
+        self
+    }
+
+    /// Also accepts `null`, for `Option`s
+    pub fn nullable(self) -> PropertySchema {
+        match self.0.get("type").cloned() {
+            Some(Value::String(json_type)) => {
+                self.insert("type", Value::from(vec![json_type, "null".to_string()]))
+            }
+            Some(_) => self,
+            None if self.0.contains_key("$ref") => {
+                let any_of = vec![Value::Object(self.0), serde_json::json!({"type": "null"})];
+                PropertySchema::default().insert("anyOf", Value::from(any_of))
+            }
+            None => self,
+        }
+    }
+
+    /// Adds the keywords of the constraints that are run on each element of the value with
+    /// `each` or on the value itself
+    pub fn constraints(mut self, constraints: &[Constraint], each: bool) -> PropertySchema {
+        let json_type = self.json_type().map(ToString::to_string);
+        for constraint in constraints.iter().filter(|c| c.each == each) {
+            add_constraint(&mut self.0, constraint, |ty| json_type.as_deref() == Some(ty));
+        }
+        self
+    }
+
+    /// The schema of the elements of an array
+    pub fn items(self, items: PropertySchema) -> PropertySchema {
+        self.insert("items", items.into_value())
+    }
+
+    /// The schema of the values of a map
+    pub fn values(self, values: PropertySchema) -> PropertySchema {
+        self.insert("additionalProperties", values.into_value())
+    }
+
+    pub fn into_value(self) -> Value {
+        Value::Object(self.0)
+    }
+}
+
+/// The constraints of a field, among the ones returned by `Constraints::constraints`
+pub fn field_constraints<'a>(constraints: &'a [FieldConstraints], field: &str) -> &'a [Constraint] {
+    constraints.iter().find(|c| c.field == field).map(|c| c.constraints.as_slice()).unwrap_or(&[])
+}
+
+/// The schema of a struct, built by the derive
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ObjectSchema {
+    properties: Map<String, Value>,
+    required: Vec<Value>,
+    keywords: Map<String, Value>,
+}
+
+impl ObjectSchema {
+    pub fn new() -> ObjectSchema {
+        ObjectSchema::default()
+    }
+
+    pub fn property(&mut self, name: &str, schema: PropertySchema, required: bool) {
+        self.properties.insert(name.to_string(), schema.into_value());
+        if required {
+            self.required.push(Value::from(name));
+        }
+    }
+
+    /// Adds the struct level constraints, the ones under `__all__`
+    pub fn constraints(&mut self, constraints: &[Constraint]) {
+        for constraint in constraints {
+            add_struct_constraint(&mut self.keywords, constraint);
+        }
+    }
+
+    pub fn into_value(self) -> Value {
+        let mut schema = Map::new();
+        schema.insert("type".to_string(), Value::from("object"));
+        schema.insert("properties".to_string(), Value::Object(self.properties));
+        if !self.required.is_empty() {
+            schema.insert("required".to_string(), Value::Array(self.required));
+        }
+        schema.extend(self.keywords);
+        Value::Object(schema)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use serde_json::json;
+
+    use super::{Map, ObjectSchema, PropertySchema, ToJsonSchema, Value};
+    use crate::{Constraint, Validator};
+
+    fn constraint(
+        validator: Validator,
+        params: &[(&'static str, serde_json::Value)],
+    ) -> Constraint {
+        let mut constraint = Constraint::new(validator.clone(), validator.code(), None, false);
+        for (name, value) in params {
+            constraint.add_param(Cow::from(*name), value);
+        }
+        constraint
+    }
+
+    fn length(params: &[(&'static str, serde_json::Value)]) -> Constraint {
+        constraint(Validator::Length { min: None, max: None, equal: None }, params)
+    }
+
+    fn regex(pattern: &str) -> Constraint {
+        constraint(Validator::Regex("RE".to_string()), &[("pattern", json!(pattern))])
+    }
+
+    struct Node;
+
+    impl ToJsonSchema for Node {
+        fn schema_name() -> Cow<'static, str> {
+            Cow::from("Node")
+        }
+
+        fn schema(defs: &mut Map<String, Value>) -> Value {
+            let mut schema = ObjectSchema::new();
+            schema.property(
+                "name",
+                PropertySchema::new(Some("string"))
+                    .constraints(&[length(&[("min", json!(1))]), regex("^[a-z]+$")], false),
+                true,
+            );
+            schema.property(
+                "children",
+                PropertySchema::new(Some("array")).items(PropertySchema::reference::<Node>(defs)),
+                false,
+            );
+            schema.into_value()
+        }
+    }
+
+    #[test]
+    fn test_property_schema() {
+        let constraints = vec![
+            length(&[("equal", json!(2))]),
+            constraint(Validator::Contains("a.b".to_string()), &[("needle", json!("a.b"))]),
+            regex("^[a-z]+$"),
+            constraint(Validator::Email, &[]),
+        ];
+        let schema =
+            PropertySchema::new(Some("string")).constraints(&constraints, false).nullable();
+        assert_eq!(
+            schema.into_value(),
+            json!({
+                "type": ["string", "null"],
+                "minLength": 2,
+                "maxLength": 2,
+                "pattern": "a\\.b",
+                "allOf": [{"pattern": "^[a-z]+$"}],
+                "format": "email",
+            })
+        );
+
+        let range = constraint(
+            Validator::Range { min: None, max: None },
+            &[("min", json!(18.0)), ("max", json!(99.5))],
+        );
+        let schema = PropertySchema::new(Some("integer")).constraints(&[range], false);
+        assert_eq!(schema.into_value(), json!({"type": "integer", "minimum": 18, "maximum": 99.5}));
+
+        let mut each = length(&[("max", json!(3))]);
+        each.each = true;
+        let constraints = vec![length(&[("min", json!(1)), ("max", json!(3))]), each];
+        let schema = PropertySchema::new(Some("array"))
+            .items(PropertySchema::new(Some("string")).constraints(&constraints, true))
+            .constraints(&constraints, false);
+        assert_eq!(
+            schema.into_value(),
+            json!({
+                "type": "array",
+                "items": {"type": "string", "maxLength": 3},
+                "minItems": 1,
+                "maxItems": 3,
+            })
+        );
+    }
+    #[test]
+    fn test_recursive_schema() {
+        assert_eq!(
+            Node::json_schema(),
+            json!({
+                "$schema": "https://json-schema.org/draft/2020-12/schema",
+                "title": "Node",
+                "type": "object",
+                "properties": {
+                    "name": {"type": "string", "minLength": 1, "pattern": "^[a-z]+$"},
+                    "children": {"type": "array", "items": {"$ref": "#/$defs/Node"}},
+                },
+                "required": ["name"],
+                "$defs": {
+                    "Node": {
+                        "type": "object",
+                        "properties": {
+                            "name": {"type": "string", "minLength": 1, "pattern": "^[a-z]+$"},
+                            "children": {"type": "array", "items": {"$ref": "#/$defs/Node"}},
+                        },
+                        "required": ["name"],
+                    }
+                }
+            })
+        );
+    }
+}
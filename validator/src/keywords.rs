@@ -0,0 +1,220 @@
+//! The JSON Schema keywords expressing the validators, shared by the `json_schema` export
+//! and the `openapi` fragments so both describe a `Constraint` the same way.
+
+use serde_json::{Map, Value};
+
+use crate::{Constraint, Validator};
+
+/// The keywords of the minimum and maximum length of each JSON type `length` applies to
+const LENGTH_KEYWORDS: [(&str, &str, &str); 3] = [
+    ("string", "minLength", "maxLength"),
+    ("array", "minItems", "maxItems"),
+    ("object", "minProperties", "maxProperties"),
+];
+
+/// Adds the keywords of a validator to the schema of a value, `applies` telling whether the value
+/// can be of the given JSON type. The validators JSON Schema can't express are ignored.
+pub(crate) fn add_constraint<F>(
+    schema: &mut Map<String, Value>,
+    constraint: &Constraint,
+    applies: F,
+) where
+    F: Fn(&str) -> bool,
+{
+    let param = |name: &str| constraint.params.get(name).cloned().map(number);
+    match constraint.validator {
+        Validator::Length { .. } => {
+            let (min, max) = match param("equal") {
+                Some(equal) => (Some(equal.clone()), Some(equal)),
+                None => (param("min"), param("max")),
+            };
+            for &(ty, min_key, max_key) in LENGTH_KEYWORDS.iter() {
+                if applies(ty) {
+                    insert(schema, min_key, min.clone());
+                    insert(schema, max_key, max.clone());
+                }
+            }
+        }
+        // Keeps a greater minimum given by `length`
+        Validator::NotEmpty => {
+            for &(ty, min_key, _) in LENGTH_KEYWORDS.iter() {
+                if applies(ty) && !schema.contains_key(min_key) {
+                    schema.insert(min_key.to_string(), Value::from(1));
+                }
+            }
+        }
+        Validator::NotBlank if applies("string") => add_pattern(schema, r"\S"),
+        Validator::Range { .. } => {
+            insert(schema, "minimum", param("min"));
+            insert(schema, "maximum", param("max"));
+        }
+        Validator::Email => insert(schema, "format", Some(Value::from("email"))),
+        Validator::Url => insert(schema, "format", Some(Value::from("uri"))),
+        Validator::Regex(_) => {
+            if let Some(Value::String(pattern)) = constraint.params.get("pattern") {
+                add_pattern(schema, pattern);
+            }
+        }
+        Validator::Contains(ref needle) => {
+            if applies("string") {
+                add_pattern(schema, &regex::escape(needle));
+            }
+            if applies("object") {
+                insert(schema, "required", Some(Value::from(vec![needle.clone()])));
+            }
+        }
+        _ => (),
+    }
+}
+
+/// Adds the keywords of a struct level presence rule to the schema of the struct, a field having
+/// a value when it is present and not `null`. The other validators are ignored.
+pub(crate) fn add_struct_constraint(schema: &mut Map<String, Value>, constraint: &Constraint) {
+    match constraint.validator {
+        Validator::AtLeastOneOf(ref fields) => {
+            let one_of_them = fields.iter().map(|field| have_values(&[field.as_str()])).collect();
+            add_keyword(schema, "anyOf", Value::Array(one_of_them));
+        }
+        Validator::ExactlyOneOf(ref fields) => {
+            let one_of_them = fields.iter().map(|field| have_values(&[field.as_str()])).collect();
+            add_keyword(schema, "oneOf", Value::Array(one_of_them));
+        }
+        Validator::MutuallyExclusive(ref fields) => {
+            let mut pairs = vec![];
+            for (i, first) in fields.iter().enumerate() {
+                for second in &fields[i + 1..] {
+                    pairs.push(have_values(&[first.as_str(), second.as_str()]));
+                }
+            }
+            let mut any_pair = Map::new();
+            any_pair.insert("anyOf".to_string(), Value::Array(pairs));
+            add_keyword(schema, "not", Value::Object(any_pair));
+        }
+        _ => (),
+    }
+}
+
+/// The schema of an object where all the fields have a value
+fn have_values(fields: &[&str]) -> Value {
+    let mut not_null = Map::new();
+    not_null.insert("type".to_string(), Value::from("null"));
+    let mut properties = Map::new();
+    for &field in fields {
+        let mut property = Map::new();
+        property.insert("not".to_string(), Value::Object(not_null.clone()));
+        properties.insert(field.to_string(), Value::Object(property));
+    }
+
+    let mut schema = Map::new();
+    schema.insert("required".to_string(), Value::from(fields.to_vec()));
+    schema.insert("properties".to_string(), Value::Object(properties));
+    Value::Object(schema)
+}
+
+/// Adds a pattern, in `allOf` if there is already one as a schema can only have one
+pub(crate) fn add_pattern(schema: &mut Map<String, Value>, pattern: &str) {
+    add_keyword(schema, "pattern", Value::from(pattern));
+}
+
+/// Adds a keyword, in `allOf` if the schema already has it
+pub(crate) fn add_keyword(schema: &mut Map<String, Value>, key: &str, value: Value) {
+    if !schema.contains_key(key) {
+        schema.insert(key.to_string(), value);
+        return;
+    }
+    let mut constraint = Map::new();
+    constraint.insert(key.to_string(), value);
+    if let Value::Array(all_of) = schema.entry("allOf").or_insert_with(|| Value::Array(vec![])) {
+        all_of.push(Value::Object(constraint));
+    }
+}
+
+fn insert(schema: &mut Map<String, Value>, key: &str, value: Option<Value>) {
+    if let Some(value) = value {
+        schema.insert(key.to_string(), value);
+    }
+}
+
+/// Numbers that are integers are written as such, eg `18` instead of `18.0`
+fn number(value: Value) -> Value {
+    match value.as_f64() {
+        Some(n) if value.is_f64() && n.fract() == 0.0 && n.abs() < 9_007_199_254_740_992.0 => {
+            Value::from(n as i64)
+        }
+        _ => value,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use serde_json::{json, Map, Value};
+
+    use super::{add_constraint, add_struct_constraint};
+    use crate::{Constraint, Validator};
+
+    fn keywords(constraint: &Constraint, json_type: &str) -> Value {
+        let mut schema = Map::new();
+        add_constraint(&mut schema, constraint, |ty| ty == json_type);
+        Value::Object(schema)
+    }
+
+    #[test]
+    fn test_numbers_are_written_as_integers_when_possible() {
+        let mut range =
+            Constraint::new(Validator::Range { min: None, max: None }, "range", None, false);
+        range.add_param(Cow::from("min"), &18.0);
+        range.add_param(Cow::from("max"), &99.5);
+        assert_eq!(keywords(&range, "integer"), json!({"minimum": 18, "maximum": 99.5}));
+    }
+
+    #[test]
+    fn test_length_keywords_depend_on_the_type() {
+        let mut length = Constraint::new(
+            Validator::Length { min: None, max: None, equal: None },
+            "length",
+            None,
+            false,
+        );
+        length.add_param(Cow::from("equal"), &2);
+        assert_eq!(keywords(&length, "string"), json!({"minLength": 2, "maxLength": 2}));
+        assert_eq!(keywords(&length, "array"), json!({"minItems": 2, "maxItems": 2}));
+        assert_eq!(keywords(&length, "integer"), json!({}));
+    }
+
+    #[test]
+    fn test_presence_rules_require_values() {
+        let fields = vec!["email".to_string(), "phone".to_string(), "fax".to_string()];
+        let has_value = |field: &str| json!({"required": [field], "properties": {field: {"not": {"type": "null"}}}});
+
+        let mut schema = Map::new();
+        for validator in &[
+            Validator::AtLeastOneOf(fields.clone()),
+            Validator::ExactlyOneOf(fields.clone()),
+            Validator::AtLeastOneOf(fields[..2].to_vec()),
+        ] {
+            let constraint = Constraint::new(validator.clone(), "presence", None, false);
+            add_struct_constraint(&mut schema, &constraint);
+        }
+        assert_eq!(
+            Value::Object(schema),
+            json!({
+                "anyOf": [has_value("email"), has_value("phone"), has_value("fax")],
+                "oneOf": [has_value("email"), has_value("phone"), has_value("fax")],
+                "allOf": [{"anyOf": [has_value("email"), has_value("phone")]}],
+            })
+        );
+
+        let mut schema = Map::new();
+        let exclusive = Validator::MutuallyExclusive(fields[1..].to_vec());
+        add_struct_constraint(&mut schema, &Constraint::new(exclusive, "presence", None, false));
+        assert_eq!(
+            Value::Object(schema),
+            json!({"not": {"anyOf": [{
+                "required": ["phone", "fax"],
+                "properties": {"phone": {"not": {"type": "null"}}, "fax": {"not": {"type": "null"}}},
+            }]}})
+        );
+    }
+}
@@ -44,6 +44,11 @@
 //! | `length`                |                                                       |
 //! | `range`                 |                                                       |
 //! | `must_match`            |                                                       |
+//! | `greater_than`          | (Compares the field to another one)                   |
+//! | `greater_or_equal`      | (Compares the field to another one)                   |
+//! | `less_than`             | (Compares the field to another one)                   |
+//! | `less_or_equal`         | (Compares the field to another one)                   |
+//! | `not_equal`             | (Compares the field to another one)                   |
 //! | `contains`              |                                                       |
 //! | `custom`                | This validator can also be used on then entire struct |
 //! | `regex`                 |                                                       |
@@ -52,6 +57,22 @@
 //! | `non_control_character` | (Required the feature `unic` to be enabled)           |
 //! | `nested`                | (Uses the validation of the field type it self)       |
 //! | `required`              |                                                       |
+//! | `required_if`           | (When another field is equal to a value)              |
+//! | `required_unless`       | (Unless another field is equal to a value)            |
+//! | `required_with`         | (When another `Option` field has a value)             |
+//! | `required_without`      | (When another `Option` field has no value)            |
+//! | `not_blank`             | (Rejects the strings with only Unicode whitespace)    |
+//! | `not_empty`             | (Works on any type implementing `HasLen`)             |
+//! | `each`                  | (Runs the given validators on each element)           |
+//! | `at_least_one_of`       | (On the struct, about its `Option` fields)            |
+//! | `exactly_one_of`        | (On the struct, about its `Option` fields)            |
+//! | `mutually_exclusive`    | (On the struct, about its `Option` fields)            |
+//!
+//! With the `json_schema` feature, the derive also implements `ToJsonSchema` to export
+//! the constraints as a JSON Schema.
+//!
+//! Values can also be normalized before validation with `#[derive(Sanitize)]` and
+//! the `#[sanitizer(trim, lowercase, ...)]` attribute.
 //!
 //! [Checkout the project README of an in-depth usage description with examples.](https://github.com/Keats/validator/blob/master/README.md)
 //!
@@ -63,12 +84,26 @@
 //! validator = { version = "0.12", features = ["derive"] }
 //! ```
 
+mod constraints;
+mod deserialization;
+pub mod formats;
+#[cfg(feature = "json_schema")]
+pub mod json_schema;
+mod keywords;
+pub mod openapi;
+mod rules;
+mod sanitization;
 mod traits;
 mod types;
+mod validated;
 mod validation;
 
 #[cfg(feature = "card")]
 pub use validation::cards::validate_credit_card;
+pub use validation::comparison::{
+    validate_greater_or_equal, validate_greater_than, validate_less_or_equal, validate_less_than,
+    validate_not_equal,
+};
 pub use validation::contains::validate_contains;
 pub use validation::email::validate_email;
 pub use validation::ip::{validate_ip, validate_ip_v4, validate_ip_v6};
@@ -76,16 +111,35 @@ pub use validation::length::validate_length;
 pub use validation::must_match::validate_must_match;
 #[cfg(feature = "unic")]
 pub use validation::non_control_character::validate_non_control_character;
+pub use validation::not_blank::validate_not_blank;
+pub use validation::not_empty::validate_not_empty;
 #[cfg(feature = "phone")]
 pub use validation::phone::validate_phone;
 pub use validation::range::validate_range;
 
 pub use validation::required::validate_required;
 pub use validation::urls::validate_url;
-pub use validation::Validator;
+pub use validation::{Validator, ValueOrPath};
+
+pub use constraints::{Constraint, Constraints, FieldConstraints};
+pub use deserialization::{from_deserializer, from_json, from_value};
+#[cfg(feature = "json_schema")]
+pub use json_schema::ToJsonSchema;
+pub use rules::{CustomFn, Rule, RuleKind, RuleSet};
+
+#[cfg(feature = "unic")]
+pub use sanitization::sanitize_nfc;
+pub use sanitization::{
+    sanitize_collapse_whitespace, sanitize_lowercase, sanitize_trim, sanitize_uppercase,
+};
 
-pub use traits::{Contains, HasLen, Validate};
-pub use types::{ValidationError, ValidationErrors, ValidationErrorsKind};
+pub use traits::{Contains, HasLen, Sanitize, Validate, ValidationResult};
+pub use types::{
+    redact_sensitive_value, reset_value_hook, set_value_hook, set_value_params, value_params,
+    TaggedValidationErrors, ValidationError, ValidationErrors, ValidationErrorsKind, ValueHook,
+    ValueParams,
+};
+pub use validated::Validated;
 
 #[cfg(feature = "derive")]
-pub use validator_derive::Validate;
+pub use validator_derive::{Sanitize, Validate};
@@ -0,0 +1,314 @@
+//! OpenAPI 3.1 schema fragments built from the validators described by `Constraints`.
+//!
+//! The validators don't know the types of the fields so the fragments only have the constraints,
+//! meant to be merged into the schemas of a spec generator. `merge_into_schema` uses the `type`
+//! of the existing property schemas to pick the keywords, eg `minLength` or `minItems` for `length`.
+
+use serde_json::{Map, Value};
+
+use crate::keywords::{add_constraint, add_keyword, add_struct_constraint};
+use crate::{Constraint, Constraints, Validator};
+
+/// The keywords of the constraints of a property, for a value of the given JSON type.
+/// Without a type, `length` and `contains` give the keywords for all the types they apply to,
+/// which is still a valid schema as those keywords are ignored for the other types.
+pub fn property_fragment<'a, I>(constraints: I, json_type: Option<&str>) -> Map<String, Value>
+where
+    I: IntoIterator<Item = &'a Constraint>,
+{
+    let mut schema = Map::new();
+    for constraint in constraints {
+        add_constraint(&mut schema, constraint, |ty| json_type.is_none() || json_type == Some(ty));
+    }
+    schema
+}
+
+/// The fragment of an object schema for `T`: `properties` has the constraints of every field that
+/// has some, the `each` ones being under `items`, and `required` the fields using `required`.
+/// The struct level presence rules like `exactly_one_of` are expressed with `anyOf`, `oneOf` and `not`.
+pub fn schema_fragment<T: Constraints>() -> Value {
+    let mut properties = Map::new();
+    let mut required = vec![];
+    let mut fragment = Map::new();
+
+    for field in T::constraints() {
+        if field.field == "__all__" {
+            for constraint in &field.constraints {
+                add_struct_constraint(&mut fragment, constraint);
+            }
+            continue;
+        }
+        if is_required(&field.constraints) {
+            required.push(Value::from(field.field));
+        }
+        let mut schema = property_fragment(field.constraints.iter().filter(|c| !c.each), None);
+        let items = property_fragment(field.constraints.iter().filter(|c| c.each), None);
+        if !items.is_empty() {
+            schema.insert("items".to_string(), Value::Object(items));
+        }
+        if !schema.is_empty() {
+            properties.insert(field.field.to_string(), Value::Object(schema));
+        }
+    }
+
+    fragment.insert("properties".to_string(), Value::Object(properties));
+    if !required.is_empty() {
+        fragment.insert("required".to_string(), Value::Array(required));
+    }
+    Value::Object(fragment)
+}
+
+/// Merges the constraints of `T` into an existing object schema of `T`, using the `type` of
+/// its properties. The constraints of fields missing from `properties` are ignored.
+pub fn merge_into_schema<T: Constraints>(schema: &mut Value) {
+    let schema = match schema.as_object_mut() {
+        Some(schema) => schema,
+        None => return,
+    };
+
+    for field in T::constraints() {
+        if field.field == "__all__" {
+            let mut fragment = Map::new();
+            for constraint in &field.constraints {
+                add_struct_constraint(&mut fragment, constraint);
+            }
+            merge(schema, fragment);
+            continue;
+        }
+
+        let property = match schema
+            .get_mut("properties")
+            .and_then(|p| p.get_mut(field.field))
+            .and_then(Value::as_object_mut)
+        {
+            Some(property) => property,
+            None => continue,
+        };
+
+        let json_type = find_type(property);
+        let fragment =
+            property_fragment(field.constraints.iter().filter(|c| !c.each), json_type.as_deref());
+        merge(property, fragment);
+
+        let each = field.constraints.iter().filter(|c| c.each).collect::<Vec<_>>();
+        if !each.is_empty() {
+            let key = if json_type.as_deref() == Some("object") {
+                "additionalProperties"
+            } else {
+                "items"
+            };
+            let elements = property.entry(key).or_insert_with(|| Value::Object(Map::new()));
+            if let Some(elements) = elements.as_object_mut() {
+                let element_type = find_type(elements);
+                merge(elements, property_fragment(each, element_type.as_deref()));
+            }
+        }
+
+        if is_required(&field.constraints) {
+            let required = schema.entry("required").or_insert_with(|| Value::Array(vec![]));
+            if let Some(required) = required.as_array_mut() {
+                let name = Value::from(field.field);
+                if !required.contains(&name) {
+                    required.push(name);
+                }
+            }
+        }
+    }
+}
+
+fn is_required(constraints: &[Constraint]) -> bool {
+    constraints.iter().any(|c| {
+        !c.each && (c.validator == Validator::Required || c.validator == Validator::RequiredNested)
+    })
+}
+
+/// The JSON type of a schema, ignoring `null` in a list of types like `["string", "null"]`
+fn find_type(schema: &Map<String, Value>) -> Option<String> {
+    match schema.get("type")? {
+        Value::String(t) => Some(t.clone()),
+        Value::Array(types) => {
+            types.iter().filter_map(Value::as_str).find(|t| *t != "null").map(ToString::to_string)
+        }
+        _ => None,
+    }
+}
+
+/// Merges a fragment into a schema, the patterns and the presence rules of both being kept
+fn merge(schema: &mut Map<String, Value>, fragment: Map<String, Value>) {
+    for (key, value) in fragment {
+        match (key.as_str(), value) {
+            ("pattern", value) | ("anyOf", value) | ("oneOf", value) | ("not", value) => {
+                add_keyword(schema, &key, value)
+            }
+            ("allOf", Value::Array(all_of)) => {
+                for constraint in all_of {
+                    if let Value::Object(constraint) = constraint {
+                        for (key, value) in constraint {
+                            add_keyword(schema, &key, value);
+                        }
+                    }
+                }
+            }
+            (_, value) => {
+                schema.insert(key, value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use serde_json::json;
+
+    use super::{merge_into_schema, property_fragment, schema_fragment};
+    use crate::{Constraint, Constraints, FieldConstraints, Validator};
+
+    fn constraint(
+        validator: Validator,
+        params: &[(&'static str, serde_json::Value)],
+    ) -> Constraint {
+        let mut constraint = Constraint::new(validator.clone(), validator.code(), None, false);
+        for (name, value) in params {
+            constraint.add_param(Cow::from(*name), value);
+        }
+        constraint
+    }
+
+    fn has_value(field: &str) -> serde_json::Value {
+        json!({"required": [field], "properties": {field: {"not": {"type": "null"}}}})
+    }
+
+    struct Signup;
+
+    impl Constraints for Signup {
+        fn constraints() -> Vec<FieldConstraints> {
+            let length = Validator::Length { min: None, max: None, equal: None };
+            let mut tag = constraint(length.clone(), &[("max", json!(10))]);
+            tag.each = true;
+            vec![
+                FieldConstraints {
+                    field: "name",
+                    constraints: vec![
+                        constraint(Validator::Required, &[]),
+                        constraint(length, &[("min", json!(1)), ("max", json!(20))]),
+                        constraint(
+                            Validator::Regex("RE".to_string()),
+                            &[("pattern", json!("^[a-z]+$"))],
+                        ),
+                    ],
+                },
+                FieldConstraints {
+                    field: "age",
+                    constraints: vec![constraint(
+                        Validator::Range { min: None, max: None },
+                        &[("min", json!(18.0))],
+                    )],
+                },
+                FieldConstraints { field: "tags", constraints: vec![tag] },
+                FieldConstraints { field: "bio", constraints: vec![] },
+                FieldConstraints {
+                    field: "__all__",
+                    constraints: vec![constraint(
+                        Validator::ExactlyOneOf(vec!["mail".to_string(), "phone".to_string()]),
+                        &[("fields", json!(["mail", "phone"]))],
+                    )],
+                },
+            ]
+        }
+    }
+
+    #[test]
+    fn test_property_fragment() {
+        let constraints = vec![
+            constraint(Validator::Contains("a.b".to_string()), &[("needle", json!("a.b"))]),
+            constraint(Validator::Email, &[]),
+        ];
+        assert_eq!(
+            serde_json::Value::Object(property_fragment(&constraints, Some("string"))),
+            json!({"pattern": "a\\.b", "format": "email"})
+        );
+        assert_eq!(
+            serde_json::Value::Object(property_fragment(&constraints, None)),
+            json!({"pattern": "a\\.b", "required": ["a.b"], "format": "email"})
+        );
+    }
+
+    #[test]
+    fn test_property_fragment_not_empty_keeps_length_minimum() {
+        let length = Validator::Length { min: None, max: None, equal: None };
+        let constraints = vec![
+            constraint(length, &[("min", json!(3))]),
+            constraint(Validator::NotEmpty, &[]),
+            constraint(Validator::NotBlank, &[]),
+        ];
+        assert_eq!(
+            serde_json::Value::Object(property_fragment(&constraints, Some("string"))),
+            json!({"minLength": 3, "pattern": "\\S"})
+        );
+        assert_eq!(
+            serde_json::Value::Object(property_fragment(&constraints, Some("array"))),
+            json!({"minItems": 3})
+        );
+    }
+
+    #[test]
+    fn test_schema_fragment() {
+        assert_eq!(
+            schema_fragment::<Signup>(),
+            json!({
+                "properties": {
+                    "name": {
+                        "minLength": 1,
+                        "maxLength": 20,
+                        "minItems": 1,
+                        "maxItems": 20,
+                        "minProperties": 1,
+                        "maxProperties": 20,
+                        "pattern": "^[a-z]+$",
+                    },
+                    "age": {"minimum": 18},
+                    "tags": {"items": {"maxLength": 10, "maxItems": 10, "maxProperties": 10}},
+                },
+                "required": ["name"],
+                "oneOf": [has_value("mail"), has_value("phone")],
+            })
+        );
+    }
+
+    #[test]
+    fn test_merge_into_schema() {
+        let mut schema = json!({
+            "type": "object",
+            "properties": {
+                "name": {"type": "string", "pattern": "^[a-z0-9]+$"},
+                "age": {"type": ["integer", "null"]},
+                "tags": {"type": "array", "items": {"type": "string"}},
+            },
+            "required": ["age"],
+            "oneOf": [{"required": ["name"]}, {"required": ["age"]}],
+        });
+        merge_into_schema::<Signup>(&mut schema);
+        assert_eq!(
+            schema,
+            json!({
+                "type": "object",
+                "properties": {
+                    "name": {
+                        "type": "string",
+                        "minLength": 1,
+                        "maxLength": 20,
+                        "pattern": "^[a-z0-9]+$",
+                        "allOf": [{"pattern": "^[a-z]+$"}],
+                    },
+                    "age": {"type": ["integer", "null"], "minimum": 18},
+                    "tags": {"type": "array", "items": {"type": "string", "maxLength": 10}},
+                },
+                "required": ["age", "name"],
+                "oneOf": [{"required": ["name"]}, {"required": ["age"]}],
+                "allOf": [{"oneOf": [has_value("mail"), has_value("phone")]}],
+            })
+        );
+    }
+}
@@ -0,0 +1,377 @@
+//! Loading rules from configuration files with serde.
+//!
+//! A `RuleSet` is a map of paths to a list of rules, a rule being either the name of a validator
+//! without parameters or a map with its `kind`, parameters, `code` and `message`:
+//!
+//! ```json
+//! {
+//!     "email": ["required", "email"],
+//!     "name": [{"kind": "length", "min": 1, "max": 100, "message": "Invalid name"}],
+//!     "items[].quantity": [{"kind": "range", "min": 1, "code": "quantity"}]
+//! }
+//! ```
+//!
+//! The parameters are `min`, `max` and `equal` for `length`, `min` and `max` for `range`,
+//! `other` for `must_match` and `pattern` for `contains` and `regex`.
+//! `custom` rules can't be loaded as they are functions.
+
+use std::borrow::Cow;
+use std::fmt;
+
+use regex::Regex;
+use serde::de::{self, value::MapAccessDeserializer, Deserialize, DeserializeSeed, Visitor};
+use serde_derive::Deserialize;
+
+use super::{Rule, RuleKind, RuleSet};
+
+const KINDS: &[&str] = &[
+    "email",
+    "url",
+    "length",
+    "range",
+    "must_match",
+    "contains",
+    "regex",
+    "required",
+    "credit_card",
+    "phone",
+    "non_control_character",
+];
+
+/// A rule as written in the configuration
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RuleConfig {
+    kind: String,
+    min: Option<f64>,
+    max: Option<f64>,
+    equal: Option<f64>,
+    other: Option<String>,
+    pattern: Option<String>,
+    code: Option<String>,
+    message: Option<String>,
+}
+
+impl RuleConfig {
+    fn from_kind(kind: &str) -> RuleConfig {
+        RuleConfig {
+            kind: kind.to_string(),
+            min: None,
+            max: None,
+            equal: None,
+            other: None,
+            pattern: None,
+            code: None,
+            message: None,
+        }
+    }
+
+    /// Checks that only the parameters in `allowed` are set
+    fn check_params(&self, allowed: &[&str]) -> Result<(), String> {
+        let params = [
+            ("min", self.min.is_some()),
+            ("max", self.max.is_some()),
+            ("equal", self.equal.is_some()),
+            ("other", self.other.is_some()),
+            ("pattern", self.pattern.is_some()),
+        ];
+        match params.iter().find(|(name, is_set)| *is_set && !allowed.contains(name)) {
+            Some((name, _)) => Err(format!("`{}` doesn't take a `{}` parameter", self.kind, name)),
+            None => Ok(()),
+        }
+    }
+
+    fn required<'a>(&self, name: &str, value: &'a Option<String>) -> Result<&'a str, String> {
+        value
+            .as_ref()
+            .map(|v| v.as_str())
+            .ok_or_else(|| format!("`{}` needs the `{}` parameter", self.kind, name))
+    }
+
+    fn into_rule(self) -> Result<Rule, String> {
+        let kind = match self.kind.as_ref() {
+            "email" | "url" | "required" | "credit_card" | "phone" | "non_control_character" => {
+                self.check_params(&[])?;
+                simple_kind(&self.kind)?
+            }
+            "length" => {
+                self.check_params(&["min", "max", "equal"])?;
+                RuleKind::Length {
+                    min: to_length(self.min, "min")?,
+                    max: to_length(self.max, "max")?,
+                    equal: to_length(self.equal, "equal")?,
+                }
+            }
+            "range" => {
+                self.check_params(&["min", "max"])?;
+                RuleKind::Range { min: self.min, max: self.max }
+            }
+            "must_match" => {
+                self.check_params(&["other"])?;
+                RuleKind::MustMatch(self.required("other", &self.other)?.to_string())
+            }
+            "contains" => {
+                self.check_params(&["pattern"])?;
+                RuleKind::Contains(self.required("pattern", &self.pattern)?.to_string())
+            }
+            "regex" => {
+                self.check_params(&["pattern"])?;
+                let pattern = self.required("pattern", &self.pattern)?;
+                let regex = Regex::new(pattern)
+                    .map_err(|e| format!("invalid regex `{}`: {}", pattern, e))?;
+                RuleKind::Regex(regex)
+            }
+            "custom" => return Err("`custom` rules can't be loaded from a configuration".into()),
+            kind => {
+                return Err(format!(
+                    "unknown rule kind `{}`, expected one of {}",
+                    kind,
+                    KINDS.iter().map(|k| format!("`{}`", k)).collect::<Vec<_>>().join(", ")
+                ))
+            }
+        };
+
+        Ok(Rule { kind, code: self.code.map(Cow::from), message: self.message.map(Cow::from) })
+    }
+}
+
+/// The kinds without parameters, some of which depend on a feature
+fn simple_kind(kind: &str) -> Result<RuleKind, String> {
+    match kind {
+        "email" => Ok(RuleKind::Email),
+        "url" => Ok(RuleKind::Url),
+        "required" => Ok(RuleKind::Required),
+        #[cfg(feature = "card")]
+        "credit_card" => Ok(RuleKind::CreditCard),
+        #[cfg(not(feature = "card"))]
+        "credit_card" => feature_error(kind, "card"),
+        #[cfg(feature = "phone")]
+        "phone" => Ok(RuleKind::Phone),
+        #[cfg(not(feature = "phone"))]
+        "phone" => feature_error(kind, "phone"),
+        #[cfg(feature = "unic")]
+        "non_control_character" => Ok(RuleKind::NonControlCharacter),
+        #[cfg(not(feature = "unic"))]
+        "non_control_character" => feature_error(kind, "unic"),
+        _ => unreachable!(),
+    }
+}
+
+/// The error for a kind needing a feature that isn't enabled
+#[allow(dead_code)]
+fn feature_error(kind: &str, feature: &str) -> Result<RuleKind, String> {
+    Err(format!("`{}` rules need the `{}` feature of validator", kind, feature))
+}
+
+fn to_length(value: Option<f64>, name: &str) -> Result<Option<u64>, String> {
+    match value {
+        Some(v) if v < 0.0 || v.fract() != 0.0 || v > u64::MAX as f64 => {
+            Err(format!("`{}` of `length` must be a positive integer, got {}", name, v))
+        }
+        Some(v) => Ok(Some(v as u64)),
+        None => Ok(None),
+    }
+}
+
+struct RuleVisitor;
+
+impl<'de> Visitor<'de> for RuleVisitor {
+    type Value = Rule;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("the name of a rule or a map with the `kind` of the rule")
+    }
+
+    fn visit_str<E: de::Error>(self, kind: &str) -> Result<Rule, E> {
+        RuleConfig::from_kind(kind).into_rule().map_err(E::custom)
+    }
+
+    fn visit_map<A: de::MapAccess<'de>>(self, map: A) -> Result<Rule, A::Error> {
+        let config = RuleConfig::deserialize(MapAccessDeserializer::new(map))?;
+        config.into_rule().map_err(de::Error::custom)
+    }
+}
+
+impl<'de> Deserialize<'de> for Rule {
+    fn deserialize<D: de::Deserializer<'de>>(deserializer: D) -> Result<Rule, D::Error> {
+        deserializer.deserialize_any(RuleVisitor)
+    }
+}
+
+/// Deserializes a rule, adding where it is to the error
+struct RuleSeed<'a> {
+    path: &'a str,
+    index: usize,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for RuleSeed<'a> {
+    type Value = Rule;
+
+    fn deserialize<D: de::Deserializer<'de>>(self, deserializer: D) -> Result<Rule, D::Error> {
+        Rule::deserialize(deserializer).map_err(|e| {
+            de::Error::custom(format_args!("rule {} of `{}`: {}", self.index, self.path, e))
+        })
+    }
+}
+
+/// Deserializes the rules of a path
+struct RulesSeed<'a> {
+    path: &'a str,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for RulesSeed<'a> {
+    type Value = Vec<Rule>;
+
+    fn deserialize<D: de::Deserializer<'de>>(self, deserializer: D) -> Result<Vec<Rule>, D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for RulesSeed<'a> {
+    type Value = Vec<Rule>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "a list of rules for `{}`", self.path)
+    }
+
+    fn visit_seq<A: de::SeqAccess<'de>>(self, mut seq: A) -> Result<Vec<Rule>, A::Error> {
+        let mut rules = vec![];
+        while let Some(rule) =
+            seq.next_element_seed(RuleSeed { path: self.path, index: rules.len() })?
+        {
+            rules.push(rule);
+        }
+        Ok(rules)
+    }
+}
+
+struct RuleSetVisitor;
+
+impl<'de> Visitor<'de> for RuleSetVisitor {
+    type Value = RuleSet;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a map of paths to a list of rules")
+    }
+
+    fn visit_map<A: de::MapAccess<'de>>(self, mut map: A) -> Result<RuleSet, A::Error> {
+        let mut rule_set = RuleSet::new();
+        while let Some(path) = map.next_key::<String>()? {
+            let rules = map.next_value_seed(RulesSeed { path: &path })?;
+            rule_set.add(&path, rules);
+        }
+        Ok(rule_set)
+    }
+}
+
+impl<'de> Deserialize<'de> for RuleSet {
+    fn deserialize<D: de::Deserializer<'de>>(deserializer: D) -> Result<RuleSet, D::Error> {
+        deserializer.deserialize_map(RuleSetVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::super::{RuleKind, RuleSet};
+
+    #[test]
+    fn test_load_rules_from_json() {
+        let rules: RuleSet = serde_json::from_str(
+            r#"{
+                "email": ["required", {"kind": "email", "code": "mail", "message": "Invalid email"}],
+                "name": [{"kind": "length", "min": 1, "max": 10}],
+                "age": [{"kind": "range", "min": 18}],
+                "password2": [{"kind": "must_match", "other": "password"}],
+                "tags[]": [{"kind": "contains", "pattern": "a"}, {"kind": "regex", "pattern": "^[a-z]+$"}]
+            }"#,
+        )
+        .unwrap();
+
+        let value = json!({
+            "name": "Bob",
+            "age": 20,
+            "password": "a",
+            "password2": "a",
+            "tags": ["abc", "bcd", "A"],
+        });
+        let errs = rules.validate(&value).unwrap_err();
+        assert_eq!(errs.errors().len(), 2);
+        assert_eq!(errs.field_errors()["email"][0].code, "required");
+
+        let errs = rules.validate(&json!({"email": "bob"})).unwrap_err();
+        let err = &errs.field_errors()["email"][0];
+        assert_eq!(err.code, "mail");
+        assert_eq!(err.message.as_ref().unwrap(), "Invalid email");
+    }
+
+    #[test]
+    fn test_load_rules_from_yaml() {
+        let rules: RuleSet = serde_yaml::from_str(
+            r#"
+email:
+  - required
+  - kind: length
+    max: 100
+items[].quantity:
+  - kind: range
+    min: 1
+    max: 10
+"#,
+        )
+        .unwrap();
+
+        assert!(rules.validate(&json!({"email": "bob", "items": [{"quantity": 1}]})).is_ok());
+        assert!(rules.validate(&json!({"email": "bob", "items": [{"quantity": 0}]})).is_err());
+    }
+
+    #[test]
+    fn test_load_rules_from_toml() {
+        let rules: RuleSet = toml::from_str(
+            r#"
+email = ["required", "email"]
+"address.city" = [{ kind = "length", equal = 5, code = "city" }]
+"#,
+        )
+        .unwrap();
+
+        assert!(matches!(
+            rules.root.fields[1].1.fields[0].1.rules[0].kind,
+            RuleKind::Length { min: None, max: None, equal: Some(5) }
+        ));
+        let errs = rules.validate(&json!({"email": "bob@bob.com", "address": {"city": "Lyon"}}));
+        assert!(errs.is_err());
+    }
+
+    #[test]
+    fn test_invalid_rules_errors() {
+        let tests = vec![
+            (r#"{"a": ["mail"]}"#, "rule 0 of `a`: unknown rule kind `mail`, expected one of"),
+            (
+                r#"{"a": ["email", {"kind": "regex", "pattern": "("}]}"#,
+                "rule 1 of `a`: invalid regex `(`",
+            ),
+            (
+                r#"{"a": [{"kind": "length", "pattern": "a"}]}"#,
+                "rule 0 of `a`: `length` doesn't take a `pattern` parameter",
+            ),
+            (
+                r#"{"a": [{"kind": "length", "min": 1.5}]}"#,
+                "rule 0 of `a`: `min` of `length` must be a positive integer, got 1.5",
+            ),
+            (
+                r#"{"a": [{"kind": "must_match"}]}"#,
+                "rule 0 of `a`: `must_match` needs the `other` parameter",
+            ),
+            (r#"{"a": [{"kind": "email", "mn": 1}]}"#, "rule 0 of `a`: unknown field `mn`"),
+            (r#"{"a": [{"min": 1}]}"#, "rule 0 of `a`: missing field `kind`"),
+            (r#"{"a": ["custom"]}"#, "rule 0 of `a`: `custom` rules can't be loaded"),
+        ];
+
+        for (config, expected) in tests {
+            let err = serde_json::from_str::<RuleSet>(config).unwrap_err().to_string();
+            assert!(err.starts_with(expected), "`{}` doesn't start with `{}`", err, expected);
+        }
+    }
+}
@@ -0,0 +1,580 @@
+//! Validation rules defined at runtime, for data that doesn't have a Rust type known at
+//! compile time, eg forms defined by users.
+//!
+//! A `RuleSet` maps paths in a `serde_json::Value` to the rules to run on the value at that path
+//! and reports the errors exactly like `#[derive(Validate)]` would for the equivalent struct.
+//! Rule sets can also be deserialized from configuration files, see the `config` module.
+
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Arc;
+
+use regex::Regex;
+use serde_json::{Map, Value};
+
+#[cfg(feature = "card")]
+use crate::validation::cards::validate_credit_card;
+use crate::validation::contains::validate_contains;
+use crate::validation::email::validate_email;
+use crate::validation::length::validate_length;
+use crate::validation::must_match::validate_must_match;
+#[cfg(feature = "unic")]
+use crate::validation::non_control_character::validate_non_control_character;
+#[cfg(feature = "phone")]
+use crate::validation::phone::validate_phone;
+use crate::validation::range::validate_range;
+use crate::validation::urls::validate_url;
+use crate::{ValidationError, ValidationErrors, ValidationErrorsKind};
+
+mod config;
+
+/// The signature of the functions used by `Rule::custom`
+pub type CustomFn = dyn Fn(&Value) -> Result<(), ValidationError> + Send + Sync;
+
+/// What a rule checks, the runtime equivalent of the validators of the derive
+#[derive(Clone)]
+pub enum RuleKind {
+    Email,
+    Url,
+    Length {
+        min: Option<u64>,
+        max: Option<u64>,
+        equal: Option<u64>,
+    },
+    Range {
+        min: Option<f64>,
+        max: Option<f64>,
+    },
+    /// The name of the field of the same object the value must be equal to
+    MustMatch(String),
+    Contains(String),
+    Regex(Regex),
+    Required,
+    #[cfg(feature = "card")]
+    CreditCard,
+    #[cfg(feature = "phone")]
+    Phone,
+    #[cfg(feature = "unic")]
+    NonControlCharacter,
+    Custom(Arc<CustomFn>),
+}
+
+impl fmt::Debug for RuleKind {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            RuleKind::Email => write!(fmt, "Email"),
+            RuleKind::Url => write!(fmt, "Url"),
+            RuleKind::Length { min, max, equal } => fmt
+                .debug_struct("Length")
+                .field("min", &min)
+                .field("max", &max)
+                .field("equal", &equal)
+                .finish(),
+            RuleKind::Range { min, max } => {
+                fmt.debug_struct("Range").field("min", &min).field("max", &max).finish()
+            }
+            RuleKind::MustMatch(ref other) => fmt.debug_tuple("MustMatch").field(other).finish(),
+            RuleKind::Contains(ref needle) => fmt.debug_tuple("Contains").field(needle).finish(),
+            RuleKind::Regex(ref re) => fmt.debug_tuple("Regex").field(re).finish(),
+            RuleKind::Required => write!(fmt, "Required"),
+            #[cfg(feature = "card")]
+            RuleKind::CreditCard => write!(fmt, "CreditCard"),
+            #[cfg(feature = "phone")]
+            RuleKind::Phone => write!(fmt, "Phone"),
+            #[cfg(feature = "unic")]
+            RuleKind::NonControlCharacter => write!(fmt, "NonControlCharacter"),
+            RuleKind::Custom(_) => write!(fmt, "Custom"),
+        }
+    }
+}
+
+impl RuleKind {
+    /// The default error code, the same as the one of the derive
+    fn code(&self) -> &'static str {
+        match *self {
+            RuleKind::Email => "email",
+            RuleKind::Url => "url",
+            RuleKind::Length { .. } => "length",
+            RuleKind::Range { .. } => "range",
+            RuleKind::MustMatch(_) => "must_match",
+            RuleKind::Contains(_) => "contains",
+            RuleKind::Regex(_) => "regex",
+            RuleKind::Required => "required",
+            #[cfg(feature = "card")]
+            RuleKind::CreditCard => "credit_card",
+            #[cfg(feature = "phone")]
+            RuleKind::Phone => "phone",
+            #[cfg(feature = "unic")]
+            RuleKind::NonControlCharacter => "non_control_character",
+            RuleKind::Custom(_) => "custom",
+        }
+    }
+}
+
+/// A validation rule with an optional error code and message
+#[derive(Debug, Clone)]
+pub struct Rule {
+    pub kind: RuleKind,
+    pub code: Option<Cow<'static, str>>,
+    pub message: Option<Cow<'static, str>>,
+}
+
+impl From<RuleKind> for Rule {
+    fn from(kind: RuleKind) -> Rule {
+        Rule { kind, code: None, message: None }
+    }
+}
+
+impl Rule {
+    pub fn email() -> Rule {
+        RuleKind::Email.into()
+    }
+
+    pub fn url() -> Rule {
+        RuleKind::Url.into()
+    }
+
+    /// Validates the number of chars of a string or the number of elements of an array or object
+    pub fn length(min: Option<u64>, max: Option<u64>, equal: Option<u64>) -> Rule {
+        RuleKind::Length { min, max, equal }.into()
+    }
+
+    pub fn range(min: Option<f64>, max: Option<f64>) -> Rule {
+        RuleKind::Range { min, max }.into()
+    }
+
+    /// Validates that the value is equal to the one of the field `other` of the same object
+    pub fn must_match(other: &str) -> Rule {
+        RuleKind::MustMatch(other.to_string()).into()
+    }
+
+    /// Validates that a string contains the needle, that an object has it as key or that an
+    /// array has it as element
+    pub fn contains(needle: &str) -> Rule {
+        RuleKind::Contains(needle.to_string()).into()
+    }
+
+    pub fn regex(regex: Regex) -> Rule {
+        RuleKind::Regex(regex).into()
+    }
+
+    /// Validates that the value is present and not null
+    pub fn required() -> Rule {
+        RuleKind::Required.into()
+    }
+
+    #[cfg(feature = "card")]
+    pub fn credit_card() -> Rule {
+        RuleKind::CreditCard.into()
+    }
+
+    #[cfg(feature = "phone")]
+    pub fn phone() -> Rule {
+        RuleKind::Phone.into()
+    }
+
+    #[cfg(feature = "unic")]
+    pub fn non_control_character() -> Rule {
+        RuleKind::NonControlCharacter.into()
+    }
+
+    pub fn custom<F>(function: F) -> Rule
+    where
+        F: Fn(&Value) -> Result<(), ValidationError> + Send + Sync + 'static,
+    {
+        RuleKind::Custom(Arc::new(function)).into()
+    }
+
+    /// Replaces the error code
+    pub fn with_code(mut self, code: impl Into<Cow<'static, str>>) -> Rule {
+        self.code = Some(code.into());
+        self
+    }
+
+    pub fn with_message(mut self, message: impl Into<Cow<'static, str>>) -> Rule {
+        self.message = Some(message.into());
+        self
+    }
+
+    /// Runs the rule on a value, `None` if it is missing or null. `object` is the object
+    /// containing the value, if any, used by `must_match`.
+    fn check(
+        &self,
+        value: Option<&Value>,
+        object: Option<&Map<String, Value>>,
+    ) -> Option<ValidationError> {
+        let value = match value {
+            Some(value) => value,
+            None if matches!(self.kind, RuleKind::Required) => {
+                return Some(self.error(&Value::Null, vec![]));
+            }
+            // Like `Option` fields in the derive, only `required` fails on a missing value
+            None => return None,
+        };
+
+        let is_valid_str = |f: &dyn Fn(&str) -> bool| value.as_str().map(f).unwrap_or(false);
+        let mut params = vec![];
+        let is_valid = match self.kind {
+            RuleKind::Email => is_valid_str(&|s| validate_email(s)),
+            RuleKind::Url => is_valid_str(&|s| validate_url(s)),
+            RuleKind::Length { min, max, equal } => {
+                params.extend(
+                    vec![("min", min), ("max", max), ("equal", equal)]
+                        .into_iter()
+                        .filter_map(|(name, param)| param.map(|p| (name, Value::from(p)))),
+                );
+                match *value {
+                    Value::String(ref s) => validate_length(s, min, max, equal),
+                    Value::Array(ref a) => validate_length(a, min, max, equal),
+                    Value::Object(ref o) => validate_length(o, min, max, equal),
+                    _ => false,
+                }
+            }
+            RuleKind::Range { min, max } => {
+                params.extend(
+                    vec![("min", min), ("max", max)]
+                        .into_iter()
+                        .filter_map(|(name, param)| param.map(|p| (name, Value::from(p)))),
+                );
+                value.as_f64().map(|v| validate_range(v, min, max)).unwrap_or(false)
+            }
+            RuleKind::MustMatch(ref other) => {
+                let other_value = object.and_then(|o| o.get(other)).unwrap_or(&Value::Null);
+                params.push(("other", other_value.clone()));
+                validate_must_match(value, other_value)
+            }
+            RuleKind::Contains(ref needle) => {
+                params.push(("needle", Value::from(needle.as_str())));
+                match *value {
+                    Value::String(ref s) => validate_contains(s, needle),
+                    Value::Object(ref o) => validate_contains(o, needle),
+                    Value::Array(ref a) => a.iter().any(|v| v.as_str() == Some(needle)),
+                    _ => false,
+                }
+            }
+            RuleKind::Regex(ref re) => is_valid_str(&|s| re.is_match(s)),
+            RuleKind::Required => true,
+            #[cfg(feature = "card")]
+            RuleKind::CreditCard => is_valid_str(&|s| validate_credit_card(s)),
+            #[cfg(feature = "phone")]
+            RuleKind::Phone => is_valid_str(&|s| validate_phone(s)),
+            #[cfg(feature = "unic")]
+            RuleKind::NonControlCharacter => is_valid_str(&|s| validate_non_control_character(s)),
+            RuleKind::Custom(ref function) => {
+                return function(value).err().map(|mut err| {
+                    if let Some(ref code) = self.code {
+                        err.code = code.clone();
+                    }
+                    if let Some(ref message) = self.message {
+                        err.message = Some(message.clone());
+                    }
+                    err.add_value_param(value);
+                    err
+                });
+            }
+        };
+
+        if is_valid {
+            None
+        } else {
+            Some(self.error(value, params))
+        }
+    }
+
+    fn error(&self, value: &Value, params: Vec<(&'static str, Value)>) -> ValidationError {
+        let mut err = ValidationError::new(self.kind.code());
+        if let Some(ref code) = self.code {
+            err.code = code.clone();
+        }
+        err.message = self.message.clone();
+        for (name, param) in params {
+            err.add_param(Cow::from(name), &param);
+        }
+        err.add_value_param(value);
+        err
+    }
+}
+
+/// The rules of a value and of the values inside of it
+#[derive(Debug, Clone, Default)]
+struct Node {
+    rules: Vec<Rule>,
+    /// The fields of the object, in the order they were added
+    fields: Vec<(Cow<'static, str>, Node)>,
+    /// The rules for every element of the array
+    elements: Option<Box<Node>>,
+}
+
+impl Node {
+    fn field(&mut self, name: &str) -> &mut Node {
+        let position = match self.fields.iter().position(|(n, _)| *n == name) {
+            Some(position) => position,
+            None => {
+                self.fields.push((Cow::from(name.to_string()), Node::default()));
+                self.fields.len() - 1
+            }
+        };
+        &mut self.fields[position].1
+    }
+
+    fn elements(&mut self) -> &mut Node {
+        self.elements.get_or_insert_with(Default::default)
+    }
+
+    /// Validates the fields of an object, adding their errors to `errors`
+    fn validate_fields(&self, object: Option<&Map<String, Value>>, errors: &mut ValidationErrors) {
+        for (name, node) in &self.fields {
+            let value = object.and_then(|o| o.get(name.as_ref())).filter(|v| !v.is_null());
+            node.validate(name, value, object, errors, false);
+        }
+    }
+
+    /// Validates the value of the field `name`, `is_element` being true if the value is an element
+    /// of an array: its errors are then added directly in `errors` like for `each` in the derive
+    fn validate(
+        &self,
+        name: &str,
+        value: Option<&Value>,
+        object: Option<&Map<String, Value>>,
+        errors: &mut ValidationErrors,
+        is_element: bool,
+    ) {
+        for rule in &self.rules {
+            if let Some(err) = rule.check(value, object) {
+                errors.add(name.to_string(), err);
+            }
+        }
+
+        // The errors of the value itself take priority over the ones of its content
+        let value = match value {
+            Some(value) if !errors.errors().contains_key(name) => value,
+            _ => return,
+        };
+
+        if let Value::Object(ref inner) = *value {
+            if !self.fields.is_empty() {
+                if is_element {
+                    self.validate_fields(Some(inner), errors);
+                } else {
+                    let mut nested = ValidationErrors::new();
+                    self.validate_fields(Some(inner), &mut nested);
+                    if !nested.is_empty() {
+                        errors.add_nested(
+                            name.to_string(),
+                            ValidationErrorsKind::Struct(Box::new(nested)),
+                        );
+                    }
+                }
+            }
+        }
+
+        if let (Some(ref node), Value::Array(ref elements)) = (&self.elements, value) {
+            let list = elements
+                .iter()
+                .enumerate()
+                .filter_map(|(index, element)| {
+                    let mut element_errors = ValidationErrors::new();
+                    let element = Some(element).filter(|v| !v.is_null());
+                    // The errors of the element itself go under `__all__`, like with `each`
+                    node.validate("__all__", element, None, &mut element_errors, true);
+                    if element_errors.is_empty() {
+                        None
+                    } else {
+                        Some((index, Box::new(element_errors)))
+                    }
+                })
+                .collect::<BTreeMap<_, _>>();
+            if !list.is_empty() && !errors.errors().contains_key(name) {
+                errors.add_nested(name.to_string(), ValidationErrorsKind::List(list));
+            }
+        }
+    }
+}
+
+/// A set of rules to validate a `serde_json::Value` with, built at runtime.
+///
+/// The rules are added for a path in the value: field names separated by dots, a field name being
+/// followed by `[]` to apply the rules to every element of the array it contains, eg `address.city`,
+/// `tags[]` or `items[].name`. The errors of the elements of an array are under `__all__` in
+/// the errors of each element, like for `each` in the derive.
+///
+/// ```
+/// use validator::{Rule, RuleSet};
+///
+/// let rules = RuleSet::new()
+///     .field("email", vec![Rule::required(), Rule::email()])
+///     .field("items[].quantity", vec![Rule::range(Some(1.0), None)]);
+///
+/// let value = serde_json::json!({"email": "bob@bob.com", "items": [{"quantity": 0}]});
+/// assert!(rules.validate(&value).is_err());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RuleSet {
+    root: Node,
+}
+
+impl RuleSet {
+    pub fn new() -> RuleSet {
+        RuleSet::default()
+    }
+
+    /// Adds rules for the value at the given path, after the ones already added for it
+    pub fn field(mut self, path: &str, rules: Vec<Rule>) -> RuleSet {
+        self.add(path, rules);
+        self
+    }
+
+    /// Adds rules for the value at the given path, after the ones already added for it
+    pub fn add(&mut self, path: &str, rules: Vec<Rule>) {
+        let mut node = &mut self.root;
+        for segment in path.split('.') {
+            let mut name = segment;
+            let mut depth = 0;
+            while name.ends_with("[]") {
+                name = &name[..name.len() - 2];
+                depth += 1;
+            }
+            node = node.field(name);
+            for _ in 0..depth {
+                node = node.elements();
+            }
+        }
+        node.rules.extend(rules);
+    }
+
+    /// Validates a value, which should be an object. Anything else is treated as an empty object.
+    pub fn validate(&self, value: &Value) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+        self.root.validate_fields(value.as_object(), &mut errors);
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use regex::Regex;
+    use serde_json::json;
+
+    use super::{Rule, RuleSet};
+    use crate::{ValidationError, ValidationErrorsKind};
+
+    #[test]
+    fn test_rules_ok() {
+        let rules = RuleSet::new()
+            .field("email", vec![Rule::required(), Rule::email()])
+            .field("site", vec![Rule::url()])
+            .field("name", vec![Rule::length(Some(1), Some(10), None)])
+            .field("age", vec![Rule::range(Some(18.0), None)])
+            .field("code", vec![Rule::regex(Regex::new("^[a-z]+$").unwrap())])
+            .field("tags", vec![Rule::contains("rust")]);
+        let value = json!({
+            "email": "bob@bob.com",
+            "name": "Bob",
+            "age": 20,
+            "code": "abc",
+            "tags": ["rust", "web"],
+        });
+
+        assert!(rules.validate(&value).is_ok());
+    }
+
+    #[test]
+    fn test_rules_errors() {
+        let rules = RuleSet::new()
+            .field("email", vec![Rule::email(), Rule::length(None, Some(5), None)])
+            .field("age", vec![Rule::range(Some(18.0), None).with_code("too_young")])
+            .field("username", vec![Rule::required().with_message("Username is required")])
+            .field("password2", vec![Rule::must_match("password")]);
+        let value = json!({"email": "bob", "age": 10, "password": "a", "password2": "b"});
+
+        let errs = rules.validate(&value).unwrap_err();
+        let errs = errs.field_errors();
+        assert_eq!(errs.len(), 4);
+        assert_eq!(errs["email"].len(), 1);
+        assert_eq!(errs["email"][0].code, "email");
+        assert_eq!(errs["email"][0].params["value"], "bob");
+        assert_eq!(errs["age"][0].code, "too_young");
+        assert_eq!(errs["age"][0].params["min"], 18.0);
+        assert_eq!(errs["username"][0].code, "required");
+        assert_eq!(errs["username"][0].message.as_ref().unwrap(), "Username is required");
+        assert_eq!(errs["password2"][0].params["other"], "a");
+    }
+
+    #[test]
+    fn test_rules_wrong_type() {
+        let rules = RuleSet::new()
+            .field("email", vec![Rule::email()])
+            .field("age", vec![Rule::range(Some(18.0), None)]);
+        let errs = rules.validate(&json!({"email": 1, "age": "20"})).unwrap_err();
+        let errs = errs.field_errors();
+        assert_eq!(errs["email"][0].code, "email");
+        assert_eq!(errs["age"][0].code, "range");
+    }
+
+    #[test]
+    fn test_rules_missing_values() {
+        let rules = RuleSet::new()
+            .field("email", vec![Rule::email()])
+            .field("address.city", vec![Rule::required()]);
+        assert!(rules.validate(&json!({"email": null})).is_ok());
+        assert!(rules.validate(&json!({"address": {"city": "Paris"}})).is_ok());
+
+        let errs = rules.validate(&json!({"address": {}})).unwrap_err();
+        if let ValidationErrorsKind::Struct(ref errs) = errs.errors()["address"] {
+            assert_eq!(errs.field_errors()["city"][0].code, "required");
+        } else {
+            panic!("Expected struct validation errors");
+        }
+    }
+
+    #[test]
+    fn test_rules_arrays() {
+        let rules = RuleSet::new()
+            .field("items", vec![Rule::length(Some(1), None, None)])
+            .field("items[].name", vec![Rule::length(Some(1), None, None)])
+            .field("tags[]", vec![Rule::email()]);
+
+        let errs = rules.validate(&json!({"items": []})).unwrap_err();
+        assert_eq!(errs.field_errors()["items"][0].code, "length");
+
+        let value = json!({"items": [{"name": "a"}, {"name": ""}], "tags": ["bob@bob.com", "bob"]});
+        let errs = rules.validate(&value).unwrap_err();
+        if let ValidationErrorsKind::List(ref errs) = errs.errors()["items"] {
+            assert_eq!(errs.len(), 1);
+            assert_eq!(errs[&1].field_errors()["name"][0].code, "length");
+        } else {
+            panic!("Expected list validation errors");
+        }
+        if let ValidationErrorsKind::List(ref errs) = errs.errors()["tags"] {
+            assert_eq!(errs.len(), 1);
+            assert_eq!(errs[&1].field_errors()["__all__"][0].code, "email");
+        } else {
+            panic!("Expected list validation errors");
+        }
+    }
+
+    #[test]
+    fn test_rules_custom() {
+        let rules = RuleSet::new().field(
+            "name",
+            vec![Rule::custom(|value| {
+                if value == "admin" {
+                    Err(ValidationError::new("reserved"))
+                } else {
+                    Ok(())
+                }
+            })],
+        );
+        assert!(rules.validate(&json!({"name": "bob"})).is_ok());
+
+        let errs = rules.validate(&json!({"name": "admin"})).unwrap_err();
+        let errs = errs.field_errors();
+        assert_eq!(errs["name"][0].code, "reserved");
+        assert_eq!(errs["name"][0].params["value"], "admin");
+    }
+}
@@ -0,0 +1,112 @@
+//! The functions used by `#[derive(Sanitize)]` to normalize a string in place.
+//! They only allocate when the string actually changes.
+
+#[cfg(feature = "unic")]
+use unic_normal::StrNormalForm;
+
+/// Removes the leading and trailing whitespace
+pub fn sanitize_trim(value: &mut String) {
+    let end = value.trim_end().len();
+    value.truncate(end);
+    let start = value.len() - value.trim_start().len();
+    if start > 0 {
+        value.drain(..start);
+    }
+}
+
+/// Converts the string to lowercase
+pub fn sanitize_lowercase(value: &mut String) {
+    if value.chars().any(char::is_uppercase) {
+        *value = value.to_lowercase();
+    }
+}
+
+/// Converts the string to uppercase
+pub fn sanitize_uppercase(value: &mut String) {
+    if value.chars().any(char::is_lowercase) {
+        *value = value.to_uppercase();
+    }
+}
+
+/// Trims the string and replaces every run of whitespace inside of it by a single space
+pub fn sanitize_collapse_whitespace(value: &mut String) {
+    let mut previous_whitespace = true;
+    let is_collapsed = value.chars().all(|c| {
+        let ok = if c.is_whitespace() { c == ' ' && !previous_whitespace } else { true };
+        previous_whitespace = c.is_whitespace();
+        ok
+    }) && !previous_whitespace;
+
+    if !is_collapsed && !value.is_empty() {
+        *value = value.split_whitespace().collect::<Vec<_>>().join(" ");
+    }
+}
+
+/// Normalizes the string to the Unicode Normalization Form C
+#[cfg(feature = "unic")]
+pub fn sanitize_nfc(value: &mut String) {
+    let normalized = value.nfc().collect::<String>();
+    if normalized != *value {
+        *value = normalized;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn apply(f: fn(&mut String), value: &str) -> String {
+        let mut value = value.to_string();
+        f(&mut value);
+        value
+    }
+
+    #[test]
+    fn test_sanitize_trim() {
+        let tests = vec![
+            ("", ""),
+            ("hello", "hello"),
+            ("  hello", "hello"),
+            ("hello \n", "hello"),
+            ("\t hello world  ", "hello world"),
+            ("\u{3000}hello\u{3000}", "hello"),
+            ("   ", ""),
+        ];
+
+        for (input, expected) in tests {
+            assert_eq!(apply(sanitize_trim, input), expected);
+        }
+    }
+
+    #[test]
+    fn test_sanitize_case() {
+        assert_eq!(apply(sanitize_lowercase, "Bob@Example.COM"), "bob@example.com");
+        assert_eq!(apply(sanitize_lowercase, "ÉCOLE"), "école");
+        assert_eq!(apply(sanitize_uppercase, "école"), "ÉCOLE");
+        assert_eq!(apply(sanitize_uppercase, "ABC 123"), "ABC 123");
+    }
+
+    #[test]
+    fn test_sanitize_collapse_whitespace() {
+        let tests = vec![
+            ("", ""),
+            ("hello world", "hello world"),
+            ("  hello   world ", "hello world"),
+            ("hello\n\tworld", "hello world"),
+            ("hello ", "hello"),
+            ("   ", ""),
+        ];
+
+        for (input, expected) in tests {
+            assert_eq!(apply(sanitize_collapse_whitespace, input), expected);
+        }
+    }
+
+    #[cfg(feature = "unic")]
+    #[test]
+    fn test_sanitize_nfc() {
+        // `e` followed by a combining acute accent
+        assert_eq!(apply(sanitize_nfc, "e\u{0301}cole"), "\u{00e9}cole");
+        assert_eq!(apply(sanitize_nfc, "école"), "école");
+    }
+}
@@ -1,7 +1,10 @@
 use std::borrow::Cow;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use std::iter::FromIterator;
 
-use crate::types::ValidationErrors;
+use serde_json::{Map, Value};
+
+use crate::types::{ValidationError, ValidationErrors, ValidationErrorsKind};
 
 /// Trait to implement if one wants to make the `length` validator
 /// work for more types
@@ -11,25 +14,37 @@ pub trait HasLen {
     fn length(&self) -> u64;
 }
 
-impl HasLen for String {
+impl<T: HasLen + ?Sized> HasLen for &T {
     fn length(&self) -> u64 {
-        self.chars().count() as u64
+        T::length(*self)
+    }
+}
+
+impl<T: HasLen + ?Sized> HasLen for Box<T> {
+    fn length(&self) -> u64 {
+        T::length(self)
     }
 }
 
-impl<'a> HasLen for &'a String {
+impl HasLen for str {
     fn length(&self) -> u64 {
         self.chars().count() as u64
     }
 }
 
-impl<'a> HasLen for &'a str {
+impl HasLen for String {
     fn length(&self) -> u64 {
         self.chars().count() as u64
     }
 }
 
 impl<'a> HasLen for Cow<'a, str> {
+    fn length(&self) -> u64 {
+        self.chars().count() as u64
+    }
+}
+
+impl<T> HasLen for [T] {
     fn length(&self) -> u64 {
         self.len() as u64
     }
@@ -40,7 +55,38 @@ impl<T> HasLen for Vec<T> {
         self.len() as u64
     }
 }
-impl<'a, T> HasLen for &'a Vec<T> {
+
+impl<T> HasLen for VecDeque<T> {
+    fn length(&self) -> u64 {
+        self.len() as u64
+    }
+}
+
+impl<T, S> HasLen for HashSet<T, S> {
+    fn length(&self) -> u64 {
+        self.len() as u64
+    }
+}
+
+impl<T> HasLen for BTreeSet<T> {
+    fn length(&self) -> u64 {
+        self.len() as u64
+    }
+}
+
+impl<K, V, S> HasLen for HashMap<K, V, S> {
+    fn length(&self) -> u64 {
+        self.len() as u64
+    }
+}
+
+impl<K, V> HasLen for BTreeMap<K, V> {
+    fn length(&self) -> u64 {
+        self.len() as u64
+    }
+}
+
+impl HasLen for Map<String, Value> {
     fn length(&self) -> u64 {
         self.len() as u64
     }
@@ -53,19 +99,25 @@ pub trait Contains {
     fn has_element(&self, needle: &str) -> bool;
 }
 
-impl Contains for String {
+impl<T: Contains + ?Sized> Contains for &T {
     fn has_element(&self, needle: &str) -> bool {
-        self.contains(needle)
+        T::has_element(*self, needle)
+    }
+}
+
+impl<T: Contains + ?Sized> Contains for Box<T> {
+    fn has_element(&self, needle: &str) -> bool {
+        T::has_element(self, needle)
     }
 }
 
-impl<'a> Contains for &'a String {
+impl Contains for str {
     fn has_element(&self, needle: &str) -> bool {
         self.contains(needle)
     }
 }
 
-impl<'a> Contains for &'a str {
+impl Contains for String {
     fn has_element(&self, needle: &str) -> bool {
         self.contains(needle)
     }
@@ -77,13 +129,19 @@ impl<'a> Contains for Cow<'a, str> {
     }
 }
 
+impl Contains for Map<String, Value> {
+    fn has_element(&self, needle: &str) -> bool {
+        self.contains_key(needle)
+    }
+}
+
 impl<S, H: ::std::hash::BuildHasher> Contains for HashMap<String, S, H> {
     fn has_element(&self, needle: &str) -> bool {
         self.contains_key(needle)
     }
 }
 
-impl<'a, S, H: ::std::hash::BuildHasher> Contains for &'a HashMap<String, S, H> {
+impl<S> Contains for BTreeMap<String, S> {
     fn has_element(&self, needle: &str) -> bool {
         self.contains_key(needle)
     }
@@ -92,10 +150,84 @@ impl<'a, S, H: ::std::hash::BuildHasher> Contains for &'a HashMap<String, S, H>
 /// The trait that `validator_derive` implements
 pub trait Validate {
     fn validate(&self) -> Result<(), ValidationErrors>;
+
+    /// Validates until the first error, which is the only one returned.
+    /// The derive doesn't run the validators after it, avoiding to build their errors.
+    fn validate_fast(&self) -> Result<(), ValidationErrors> {
+        self.validate()
+    }
 }
 
 impl<T: Validate> Validate for &T {
     fn validate(&self) -> Result<(), ValidationErrors> {
         T::validate(*self)
     }
+
+    fn validate_fast(&self) -> Result<(), ValidationErrors> {
+        T::validate_fast(*self)
+    }
+}
+
+/// The types `custom` and `schema` functions can return
+pub trait ValidationResult {
+    /// The errors of the result: the `ValidationError`s are put under `field`, `__all__` for
+    /// `schema` functions and the elements of `each`, while `ValidationErrors` are about the
+    /// value of `field` and nested under it, except for `__all__` where they are kept as they are,
+    /// their keys being the fields of the struct or the parts of the element validated
+    fn into_errors(self, field: &'static str) -> Result<(), ValidationErrors>;
+}
+
+impl ValidationResult for Result<(), ValidationError> {
+    fn into_errors(self, field: &'static str) -> Result<(), ValidationErrors> {
+        self.map_err(|error| ValidationErrors::from_iter(Some((field, error))))
+    }
+}
+
+impl ValidationResult for Result<(), Vec<ValidationError>> {
+    fn into_errors(self, field: &'static str) -> Result<(), ValidationErrors> {
+        self.or_else(|errors| errors.into_errors(field))
+    }
+}
+
+impl ValidationResult for Vec<ValidationError> {
+    fn into_errors(self, field: &'static str) -> Result<(), ValidationErrors> {
+        if self.is_empty() {
+            return Ok(());
+        }
+        Err(self.into_iter().map(|error| (field, error)).collect())
+    }
+}
+
+impl ValidationResult for Result<(), ValidationErrors> {
+    fn into_errors(self, field: &'static str) -> Result<(), ValidationErrors> {
+        if field == "__all__" {
+            return self;
+        }
+        self.map_err(|errors| {
+            let mut nested = ValidationErrors::new();
+            nested.add_nested(field, ValidationErrorsKind::Struct(Box::new(errors)));
+            nested
+        })
+    }
+}
+
+/// The trait that `#[derive(Sanitize)]` implements: it normalizes the values of a struct in place,
+/// typically before validating it
+pub trait Sanitize {
+    fn sanitize(&mut self);
+
+    /// Sanitizes the struct and then validates it
+    fn sanitize_and_validate(&mut self) -> Result<(), ValidationErrors>
+    where
+        Self: Validate,
+    {
+        self.sanitize();
+        self.validate()
+    }
+}
+
+impl<T: Sanitize + ?Sized> Sanitize for &mut T {
+    fn sanitize(&mut self) {
+        T::sanitize(*self)
+    }
 }
@@ -1,8 +1,16 @@
 use std::borrow::Cow;
-use std::collections::{hash_map::Entry::Vacant, BTreeMap, HashMap};
+use std::collections::hash_map::Entry::{Occupied, Vacant};
+use std::collections::{btree_map, BTreeMap, HashMap};
+use std::iter::FromIterator;
+use std::mem;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::RwLock;
+
+use lazy_static::lazy_static;
 use std::{self, fmt};
 
-use serde::ser::Serialize;
+use serde::de::{self, Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde::ser::{Impossible, Serialize, Serializer};
 use serde_derive::{Deserialize, Serialize};
 use serde_json::{to_value, Value};
 
@@ -14,12 +22,296 @@ pub struct ValidationError {
 }
 
 impl ValidationError {
-    pub fn new(code: &'static str) -> ValidationError {
-        ValidationError { code: Cow::from(code), message: None, params: HashMap::new() }
+    pub fn new(code: impl Into<Cow<'static, str>>) -> ValidationError {
+        ValidationError { code: code.into(), message: None, params: HashMap::new() }
+    }
+
+    /// Sets the message of the error, eg `ValidationError::new("reserved").with_message("This name is taken")`
+    pub fn with_message(mut self, message: impl Into<Cow<'static, str>>) -> ValidationError {
+        self.message = Some(message.into());
+        self
+    }
+
+    /// Adds a param to the error, eg `ValidationError::new("range").with_param("min", &18)`
+    pub fn with_param<T: Serialize + ?Sized>(
+        mut self,
+        name: impl Into<Cow<'static, str>>,
+        val: &T,
+    ) -> ValidationError {
+        self.add_param(name, val);
+        self
+    }
+
+    pub fn add_param<T: Serialize + ?Sized>(
+        &mut self,
+        name: impl Into<Cow<'static, str>>,
+        val: &T,
+    ) {
+        self.params.insert(name.into(), to_value(val).unwrap());
+    }
+
+    /// Adds the invalid value as the `value` param, following the policy set with `set_value_params`
+    /// and the hook set with `set_value_hook`
+    pub fn add_value_param<T: Serialize + ?Sized>(&mut self, val: &T) {
+        self.insert_value_param(val, false);
+    }
+
+    /// Adds the invalid value of a `sensitive` field as the `value` param, redacted by the hook
+    /// set with `set_value_hook`
+    pub fn add_sensitive_value_param<T: Serialize + ?Sized>(&mut self, val: &T) {
+        self.insert_value_param(val, true);
+    }
+
+    fn insert_value_param<T: Serialize + ?Sized>(&mut self, val: &T, sensitive: bool) {
+        let max = match value_params() {
+            ValueParams::Full => None,
+            ValueParams::Truncate(max) => Some(max),
+            ValueParams::Omit => return,
+        };
+        let hook = *VALUE_HOOK.read().unwrap_or_else(|e| e.into_inner());
+        let value = match (hook, max) {
+            // The default hook keeps the values of the fields that aren't sensitive as they are,
+            // so their strings can be cut before being copied
+            (None, Some(max)) if !sensitive => {
+                val.serialize(TruncatedStr(max)).unwrap_or_else(|_| to_value(val).unwrap())
+            }
+            _ => to_value(val).unwrap(),
+        };
+        let value = match hook.unwrap_or(redact_sensitive_value)(&self.code, value, sensitive) {
+            Some(value) => value,
+            None => return,
+        };
+        let value = match max {
+            Some(max) => truncate(value, max),
+            None => value,
+        };
+        self.params.insert(Cow::from("value"), value);
+    }
+}
+
+/// Decides the `value` param of an error from its code, the invalid value and whether the field
+/// is `sensitive`, returning `None` to omit it
+pub type ValueHook = fn(code: &str, value: Value, sensitive: bool) -> Option<Value>;
+
+lazy_static! {
+    /// `None` for `redact_sensitive_value`, the default
+    static ref VALUE_HOOK: RwLock<Option<ValueHook>> = RwLock::new(None);
+}
+
+/// Sets the hook deciding the `value` param of the errors, for the whole program.
+/// The hook gets the whole value, the `ValueParams::Truncate` policy being applied to what it returns.
+///
+/// The hook is process-global state shared by every thread and every crate using `validator`:
+/// set it once at startup. Tests changing it affect the other tests running at the same time.
+pub fn set_value_hook(hook: ValueHook) {
+    *VALUE_HOOK.write().unwrap_or_else(|e| e.into_inner()) = Some(hook);
+}
+
+/// Restores the default hook, `redact_sensitive_value`. Unlike setting it with `set_value_hook`,
+/// this lets `ValueParams::Truncate` cut the strings before they are copied.
+pub fn reset_value_hook() {
+    *VALUE_HOOK.write().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
+/// The default `ValueHook`: the values of the fields that aren't sensitive are kept as is,
+/// a sensitive `credit_card` number only shows its last 4 characters, eg `****1234`,
+/// and the other sensitive values are omitted
+pub fn redact_sensitive_value(code: &str, value: Value, sensitive: bool) -> Option<Value> {
+    if !sensitive {
+        return Some(value);
+    }
+
+    match (code, value) {
+        ("credit_card", Value::String(card)) => {
+            let count = card.chars().count();
+            let last = card.chars().skip(count.saturating_sub(4)).collect::<String>();
+            Some(Value::String(format!("****{}", if count > 4 { last.as_str() } else { "" })))
+        }
+        _ => None,
+    }
+}
+
+/// What the errors get as their `value` param, the invalid value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueParams {
+    /// The whole value, the default
+    Full,
+    /// The value with its strings cut to that many characters and its arrays and objects
+    /// to that many elements
+    Truncate(usize),
+    /// No `value` param: the value isn't serialized at all, which is the cheapest
+    Omit,
+}
+
+/// 0 is `Full`, 1 is `Omit` and `n + 2` is `Truncate(n)`
+static VALUE_PARAMS: AtomicUsize = AtomicUsize::new(0);
+
+/// Sets what the errors get as their `value` param, for the whole program.
+/// Like the hook of `set_value_hook`, this is process-global state: set it once at startup.
+pub fn set_value_params(policy: ValueParams) {
+    let policy = match policy {
+        ValueParams::Full => 0,
+        ValueParams::Omit => 1,
+        ValueParams::Truncate(max) => max.saturating_add(2),
+    };
+    VALUE_PARAMS.store(policy, Ordering::Relaxed);
+}
+
+/// What the errors get as their `value` param, set with `set_value_params`
+pub fn value_params() -> ValueParams {
+    match VALUE_PARAMS.load(Ordering::Relaxed) {
+        0 => ValueParams::Full,
+        1 => ValueParams::Omit,
+        max => ValueParams::Truncate(max - 2),
+    }
+}
+
+fn truncate(value: Value, max: usize) -> Value {
+    match value {
+        Value::String(s) => match s.char_indices().nth(max) {
+            Some((end, _)) => Value::String(s[..end].to_string()),
+            None => Value::String(s),
+        },
+        Value::Array(values) => {
+            Value::Array(values.into_iter().take(max).map(|v| truncate(v, max)).collect())
+        }
+        Value::Object(values) => Value::Object(
+            values.into_iter().take(max).map(|(k, v)| (k, truncate(v, max))).collect(),
+        ),
+        value => value,
+    }
+}
+
+/// Serializes a string, or a newtype or `Some` around one, to a `Value` of its first characters
+/// without copying the rest of it. It fails on every other type, which are truncated after
+/// being serialized.
+struct TruncatedStr(usize);
+
+#[derive(Debug)]
+struct NotAStr;
+
+impl fmt::Display for NotAStr {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(fmt, "not a string")
+    }
+}
+
+impl std::error::Error for NotAStr {}
+
+impl serde::ser::Error for NotAStr {
+    fn custom<T: fmt::Display>(_msg: T) -> NotAStr {
+        NotAStr
+    }
+}
+
+macro_rules! not_a_str {
+    ($($method:ident($($arg:ty),*);)*) => {
+        $(fn $method(self, $(_: $arg),*) -> Result<Value, NotAStr> {
+            Err(NotAStr)
+        })*
+    };
+}
+
+impl serde::Serializer for TruncatedStr {
+    type Ok = Value;
+    type Error = NotAStr;
+    type SerializeSeq = Impossible<Value, NotAStr>;
+    type SerializeTuple = Impossible<Value, NotAStr>;
+    type SerializeTupleStruct = Impossible<Value, NotAStr>;
+    type SerializeTupleVariant = Impossible<Value, NotAStr>;
+    type SerializeMap = Impossible<Value, NotAStr>;
+    type SerializeStruct = Impossible<Value, NotAStr>;
+    type SerializeStructVariant = Impossible<Value, NotAStr>;
+
+    fn serialize_str(self, v: &str) -> Result<Value, NotAStr> {
+        let end = v.char_indices().nth(self.0).map_or(v.len(), |(end, _)| end);
+        Ok(Value::String(v[..end].to_string()))
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Value, NotAStr> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        value: &T,
+    ) -> Result<Value, NotAStr> {
+        value.serialize(self)
+    }
+
+    not_a_str! {
+        serialize_bool(bool);
+        serialize_i8(i8);
+        serialize_i16(i16);
+        serialize_i32(i32);
+        serialize_i64(i64);
+        serialize_u8(u8);
+        serialize_u16(u16);
+        serialize_u32(u32);
+        serialize_u64(u64);
+        serialize_f32(f32);
+        serialize_f64(f64);
+        serialize_char(char);
+        serialize_bytes(&[u8]);
+        serialize_none();
+        serialize_unit();
+        serialize_unit_struct(&'static str);
+        serialize_unit_variant(&'static str, u32, &'static str);
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: &T,
+    ) -> Result<Value, NotAStr> {
+        Err(NotAStr)
     }
 
-    pub fn add_param<T: Serialize>(&mut self, name: Cow<'static, str>, val: &T) {
-        self.params.insert(name, to_value(val).unwrap());
+    fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq, NotAStr> {
+        Err(NotAStr)
+    }
+
+    fn serialize_tuple(self, _: usize) -> Result<Self::SerializeTuple, NotAStr> {
+        Err(NotAStr)
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleStruct, NotAStr> {
+        Err(NotAStr)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleVariant, NotAStr> {
+        Err(NotAStr)
+    }
+
+    fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, NotAStr> {
+        Err(NotAStr)
+    }
+
+    fn serialize_struct(self, _: &'static str, _: usize) -> Result<Self::SerializeStruct, NotAStr> {
+        Err(NotAStr)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStructVariant, NotAStr> {
+        Err(NotAStr)
     }
 }
 
@@ -38,6 +330,10 @@ impl std::error::Error for ValidationError {
     }
 }
 
+/// Serialized without a tag: `Field` is an array of errors, `Struct` an object of the errors
+/// of the fields and `List` an object of the errors of the elements keyed by their index.
+/// As `List` and `Struct` can't be told apart, the objects are deserialized as `Struct`s:
+/// `TaggedValidationErrors` gives a representation that deserializes back to the same kinds.
 #[derive(Debug, Serialize, Clone, PartialEq)]
 #[serde(untagged)]
 pub enum ValidationErrorsKind {
@@ -47,7 +343,7 @@ pub enum ValidationErrorsKind {
 }
 
 #[derive(Default, Debug, Serialize, Clone, PartialEq)]
-pub struct ValidationErrors(HashMap<&'static str, ValidationErrorsKind>);
+pub struct ValidationErrors(HashMap<Cow<'static, str>, ValidationErrorsKind>);
 
 impl ValidationErrors {
     pub fn new() -> ValidationErrors {
@@ -58,7 +354,7 @@ impl ValidationErrors {
     /// given field. May be used as a condition for performing nested struct validations on a field
     /// in the absence of field-level validation errors.
     #[must_use]
-    pub fn has_error(result: &Result<(), ValidationErrors>, field: &'static str) -> bool {
+    pub fn has_error(result: &Result<(), ValidationErrors>, field: &str) -> bool {
         match result {
             Ok(()) => false,
             Err(ref errs) => errs.contains_key(field),
@@ -69,7 +365,7 @@ impl ValidationErrors {
     /// validation result for one of its fields.
     pub fn merge(
         parent: Result<(), ValidationErrors>,
-        field: &'static str,
+        field: impl Into<Cow<'static, str>>,
         child: Result<(), ValidationErrors>,
     ) -> Result<(), ValidationErrors> {
         match child {
@@ -85,15 +381,18 @@ impl ValidationErrors {
 
     /// Returns the combined outcome of a struct's validation result along with the nested
     /// validation result for one of its fields where that field is a vector of validating structs.
+    /// The `children` have their errors under `field`, as returned by `merge`; the errors of
+    /// the elements themselves, like the ones of `each`, are under `__all__` in there.
     pub fn merge_all(
         parent: Result<(), ValidationErrors>,
-        field: &'static str,
+        field: impl Into<Cow<'static, str>>,
         children: Vec<Result<(), ValidationErrors>>,
     ) -> Result<(), ValidationErrors> {
+        let field = field.into();
         let errors = children
             .into_iter()
             .enumerate()
-            .filter_map(|(i, res)| res.err().map(|mut err| (i, err.remove(field))))
+            .filter_map(|(i, res)| res.err().map(|mut err| (i, err.remove(&field))))
             .filter_map(|(i, entry)| match entry {
                 Some(ValidationErrorsKind::Struct(errors)) => Some((i, errors)),
                 _ => None,
@@ -110,24 +409,51 @@ impl ValidationErrors {
         }
     }
 
+    /// Returns the combined outcome of a struct's validation result along with the validation
+    /// results of the values of a map field, the errors being keyed by the map keys.
+    pub fn merge_map(
+        parent: Result<(), ValidationErrors>,
+        field: impl Into<Cow<'static, str>>,
+        children: Vec<(String, Result<(), ValidationErrors>)>,
+    ) -> Result<(), ValidationErrors> {
+        let field = field.into();
+        let mut errors = ValidationErrors::new();
+        for (key, res) in children {
+            if let Some(ValidationErrorsKind::Struct(child)) =
+                res.err().and_then(|mut err| err.remove(&field))
+            {
+                errors.add_nested(key, ValidationErrorsKind::Struct(child));
+            }
+        }
+
+        if errors.is_empty() {
+            parent
+        } else {
+            parent.and_then(|_| Err(ValidationErrors::new())).map_err(|mut parent_errors| {
+                parent_errors.add_nested(field, ValidationErrorsKind::Struct(Box::new(errors)));
+                parent_errors
+            })
+        }
+    }
+
     /// Returns a map of field-level validation errors found for the struct that was validated and
     /// any of it's nested structs that are tagged for validation.
-    pub fn errors(&self) -> &HashMap<&'static str, ValidationErrorsKind> {
+    pub fn errors(&self) -> &HashMap<Cow<'static, str>, ValidationErrorsKind> {
         &self.0
     }
 
     /// Consume the struct, returning the validation errors found
-    pub fn into_errors(self) -> HashMap<&'static str, ValidationErrorsKind> {
+    pub fn into_errors(self) -> HashMap<Cow<'static, str>, ValidationErrorsKind> {
         self.0
     }
 
     /// Returns a map of only field-level validation errors found for the struct that was validated.
-    pub fn field_errors(&self) -> HashMap<&'static str, &Vec<ValidationError>> {
+    pub fn field_errors(&self) -> HashMap<Cow<'static, str>, &Vec<ValidationError>> {
         self.0
             .iter()
             .filter_map(|(k, v)| {
                 if let ValidationErrorsKind::Field(errors) = v {
-                    Some((*k, errors))
+                    Some((k.clone(), errors))
                 } else {
                     None
                 }
@@ -135,13 +461,28 @@ impl ValidationErrors {
             .collect::<HashMap<_, _>>()
     }
 
-    pub fn add(&mut self, field: &'static str, error: ValidationError) {
-        if let ValidationErrorsKind::Field(ref mut vec) =
-            self.0.entry(field).or_insert_with(|| ValidationErrorsKind::Field(vec![]))
-        {
-            vec.push(error);
-        } else {
-            panic!("Attempt to add field validation to a non-Field ValidationErrorsKind instance");
+    /// The field-level errors of the given field, to edit them
+    pub fn field_errors_mut(&mut self, field: &str) -> Option<&mut Vec<ValidationError>> {
+        match self.0.get_mut(field) {
+            Some(ValidationErrorsKind::Field(errors)) => Some(errors),
+            _ => None,
+        }
+    }
+
+    /// Adds an error to a field. When the field already has nested errors, the error goes under
+    /// their `__all__` key, see `merge_with`.
+    pub fn add(&mut self, field: impl Into<Cow<'static, str>>, error: ValidationError) {
+        let field = field.into();
+        match self.0.get_mut(&field) {
+            Some(ValidationErrorsKind::Field(errors)) => errors.push(error),
+            None => {
+                self.0.insert(field, ValidationErrorsKind::Field(vec![error]));
+            }
+            Some(_) => {
+                let mut errors = ValidationErrors::new();
+                errors.0.insert(field, ValidationErrorsKind::Field(vec![error]));
+                self.merge_with(errors);
+            }
         }
     }
 
@@ -150,24 +491,126 @@ impl ValidationErrors {
         self.0.is_empty()
     }
 
-    fn add_nested(&mut self, field: &'static str, errors: ValidationErrorsKind) {
-        if let Vacant(entry) = self.0.entry(field) {
+    /// Adds the errors of a nested struct (`ValidationErrorsKind::Struct`) or of the elements of
+    /// a collection (`ValidationErrorsKind::List`) for a field, like `#[validate]` does.
+    /// Panics if the field already has errors, see `merge_with` to combine errors.
+    pub fn add_nested(
+        &mut self,
+        field: impl Into<Cow<'static, str>>,
+        errors: ValidationErrorsKind,
+    ) {
+        if let Vacant(entry) = self.0.entry(field.into()) {
             entry.insert(errors);
         } else {
             panic!("Attempt to replace non-empty ValidationErrors entry");
         }
     }
 
+    /// Adds all the errors of `other`, the errors of the fields in both being combined:
+    /// the field errors are appended and the nested errors are merged recursively.
+    /// When a field has errors of different kinds in both, they are combined as the errors of a
+    /// nested struct: the field errors go under its `__all__` key and the errors of the elements
+    /// of a list under their index, so they keep the same JSON pointers.
+    pub fn merge_with(&mut self, other: ValidationErrors) {
+        for (field, kind) in other.0 {
+            match self.0.entry(field) {
+                Vacant(entry) => {
+                    entry.insert(kind);
+                }
+                Occupied(mut entry) => match (entry.get_mut(), kind) {
+                    (ValidationErrorsKind::Field(errors), ValidationErrorsKind::Field(other)) => {
+                        errors.extend(other)
+                    }
+                    (ValidationErrorsKind::Struct(errors), ValidationErrorsKind::Struct(other)) => {
+                        errors.merge_with(*other)
+                    }
+                    (ValidationErrorsKind::List(list), ValidationErrorsKind::List(other)) => {
+                        for (index, other) in other {
+                            match list.entry(index) {
+                                btree_map::Entry::Vacant(entry) => {
+                                    entry.insert(other);
+                                }
+                                btree_map::Entry::Occupied(mut entry) => {
+                                    entry.get_mut().merge_with(*other)
+                                }
+                            }
+                        }
+                    }
+                    (_, other) => {
+                        let kind =
+                            mem::replace(entry.get_mut(), ValidationErrorsKind::Field(vec![]));
+                        let mut errors = ValidationErrors::from_kind(kind);
+                        errors.merge_with(ValidationErrors::from_kind(other));
+                        *entry.get_mut() = ValidationErrorsKind::Struct(Box::new(errors));
+                    }
+                },
+            }
+        }
+    }
+
+    /// The errors of a field as the errors of a nested struct, see `merge_with`
+    fn from_kind(kind: ValidationErrorsKind) -> ValidationErrors {
+        match kind {
+            ValidationErrorsKind::Struct(errors) => *errors,
+            ValidationErrorsKind::List(list) => ValidationErrors(
+                list.into_iter()
+                    .map(|(index, errors)| {
+                        (Cow::from(index.to_string()), ValidationErrorsKind::Struct(errors))
+                    })
+                    .collect(),
+            ),
+            ValidationErrorsKind::Field(errors) => {
+                let mut struct_errors = ValidationErrors::new();
+                struct_errors.0.insert(Cow::from("__all__"), ValidationErrorsKind::Field(errors));
+                struct_errors
+            }
+        }
+    }
+
     #[must_use]
-    fn contains_key(&self, field: &'static str) -> bool {
+    fn contains_key(&self, field: &str) -> bool {
         self.0.contains_key(field)
     }
 
-    fn remove(&mut self, field: &'static str) -> Option<ValidationErrorsKind> {
+    fn remove(&mut self, field: &str) -> Option<ValidationErrorsKind> {
         self.0.remove(field)
     }
 }
 
+impl<K: Into<Cow<'static, str>>> Extend<(K, ValidationError)> for ValidationErrors {
+    fn extend<I: IntoIterator<Item = (K, ValidationError)>>(&mut self, iter: I) {
+        for (field, error) in iter {
+            self.add(field, error);
+        }
+    }
+}
+
+/// Merges the errors with `merge_with`, which never panics
+impl Extend<ValidationErrors> for ValidationErrors {
+    fn extend<I: IntoIterator<Item = ValidationErrors>>(&mut self, iter: I) {
+        for errors in iter {
+            self.merge_with(errors);
+        }
+    }
+}
+
+impl<K: Into<Cow<'static, str>>> FromIterator<(K, ValidationError)> for ValidationErrors {
+    fn from_iter<I: IntoIterator<Item = (K, ValidationError)>>(iter: I) -> ValidationErrors {
+        let mut errors = ValidationErrors::new();
+        errors.extend(iter);
+        errors
+    }
+}
+
+/// Merges the errors with `merge_with`, which never panics
+impl FromIterator<ValidationErrors> for ValidationErrors {
+    fn from_iter<I: IntoIterator<Item = ValidationErrors>>(iter: I) -> ValidationErrors {
+        let mut errors = ValidationErrors::new();
+        errors.extend(iter);
+        errors
+    }
+}
+
 impl std::error::Error for ValidationErrors {
     fn description(&self) -> &str {
         "Validation failed"
@@ -182,3 +625,121 @@ impl fmt::Display for ValidationErrors {
         fmt::Debug::fmt(self, fmt)
     }
 }
+
+impl<'de> Deserialize<'de> for ValidationErrorsKind {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(KindVisitor)
+    }
+}
+
+struct KindVisitor;
+
+impl<'de> Visitor<'de> for KindVisitor {
+    type Value = ValidationErrorsKind;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an array of validation errors or a map of nested validation errors")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut errors = Vec::new();
+        while let Some(error) = seq.next_element()? {
+            errors.push(error);
+        }
+        Ok(ValidationErrorsKind::Field(errors))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error> {
+        let errors = ValidationErrors::deserialize(de::value::MapAccessDeserializer::new(map))?;
+        Ok(ValidationErrorsKind::Struct(Box::new(errors)))
+    }
+}
+
+impl<'de> Deserialize<'de> for ValidationErrors {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let errors = HashMap::<String, ValidationErrorsKind>::deserialize(deserializer)?;
+        Ok(ValidationErrors(errors.into_iter().map(|(key, kind)| (Cow::from(key), kind)).collect()))
+    }
+}
+
+/// `ValidationErrors` in a tagged representation, which deserializes back to the same errors
+/// unlike the default one: each kind is an object with a single key, `field`, `struct` or `list`,
+/// eg `{"name": {"field": [...]}, "items": {"list": {"1": {"name": {"field": [...]}}}}}`
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct TaggedValidationErrors(pub ValidationErrors);
+
+impl From<ValidationErrors> for TaggedValidationErrors {
+    fn from(errors: ValidationErrors) -> TaggedValidationErrors {
+        TaggedValidationErrors(errors)
+    }
+}
+
+impl Serialize for TaggedValidationErrors {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        TaggedErrorsRef(&self.0).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for TaggedValidationErrors {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let errors = HashMap::<String, TaggedKind>::deserialize(deserializer)?;
+        let errors = errors.into_iter().map(|(key, kind)| (Cow::from(key), kind.into())).collect();
+        Ok(TaggedValidationErrors(ValidationErrors(errors)))
+    }
+}
+
+struct TaggedErrorsRef<'a>(&'a ValidationErrors);
+
+impl<'a> Serialize for TaggedErrorsRef<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.0 .0.iter().map(|(key, kind)| (key, TaggedKindRef(kind))))
+    }
+}
+
+struct TaggedKindRef<'a>(&'a ValidationErrorsKind);
+
+impl<'a> Serialize for TaggedKindRef<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let name = "ValidationErrorsKind";
+        match *self.0 {
+            ValidationErrorsKind::Struct(ref errors) => {
+                serializer.serialize_newtype_variant(name, 0, "struct", &TaggedErrorsRef(errors))
+            }
+            ValidationErrorsKind::List(ref list) => {
+                serializer.serialize_newtype_variant(name, 1, "list", &TaggedListRef(list))
+            }
+            ValidationErrorsKind::Field(ref errors) => {
+                serializer.serialize_newtype_variant(name, 2, "field", errors)
+            }
+        }
+    }
+}
+
+struct TaggedListRef<'a>(&'a BTreeMap<usize, Box<ValidationErrors>>);
+
+impl<'a> Serialize for TaggedListRef<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer
+            .collect_map(self.0.iter().map(|(index, errors)| (index, TaggedErrorsRef(errors))))
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum TaggedKind {
+    Struct(TaggedValidationErrors),
+    List(BTreeMap<usize, TaggedValidationErrors>),
+    Field(Vec<ValidationError>),
+}
+
+impl From<TaggedKind> for ValidationErrorsKind {
+    fn from(kind: TaggedKind) -> ValidationErrorsKind {
+        match kind {
+            TaggedKind::Struct(errors) => ValidationErrorsKind::Struct(Box::new(errors.0)),
+            TaggedKind::List(list) => ValidationErrorsKind::List(
+                list.into_iter().map(|(index, errors)| (index, Box::new(errors.0))).collect(),
+            ),
+            TaggedKind::Field(errors) => ValidationErrorsKind::Field(errors),
+        }
+    }
+}
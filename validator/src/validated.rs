@@ -0,0 +1,103 @@
+use std::ops::Deref;
+
+#[cfg(feature = "validated_deserialize")]
+use serde::de::{Deserialize, Deserializer, Error};
+use serde::ser::{Serialize, Serializer};
+
+use crate::traits::Validate;
+use crate::types::ValidationErrors;
+
+/// A value that has been validated.
+///
+/// It can only be created by `Validated::new`, which validates the value, and doesn't give
+/// mutable access to it so functions taking a `Validated<T>` know that the value is valid.
+///
+/// With the `validated_deserialize` feature, it deserializes like `T` and then validates the value,
+/// the `ValidationErrors` being turned into a deserialization error.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Validated<T>(T);
+
+impl<T: Validate> Validated<T> {
+    /// Validates the value, returning the errors if it is invalid
+    pub fn new(value: T) -> Result<Validated<T>, ValidationErrors> {
+        value.validate()?;
+        Ok(Validated(value))
+    }
+}
+
+impl<T> Validated<T> {
+    /// Returns the validated value
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for Validated<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> AsRef<T> for Validated<T> {
+    fn as_ref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: Serialize> Serialize for Validated<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "validated_deserialize")]
+impl<'de, T: Deserialize<'de> + Validate> Deserialize<'de> for Validated<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = T::deserialize(deserializer)?;
+        Validated::new(value).map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_derive::{Deserialize, Serialize};
+
+    use super::Validated;
+    use crate::{Validate, ValidationError, ValidationErrors};
+
+    #[derive(Debug, Serialize, Deserialize)]
+    struct Age(u8);
+
+    impl Validate for Age {
+        fn validate(&self) -> Result<(), ValidationErrors> {
+            let mut errors = ValidationErrors::new();
+            if self.0 < 18 {
+                errors.add("age", ValidationError::new("range"));
+                return Err(errors);
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_validated_new() {
+        let age = Validated::new(Age(20)).unwrap();
+        assert_eq!((*age).0, 20);
+        assert_eq!(age.into_inner().0, 20);
+
+        let errs = Validated::new(Age(10)).unwrap_err();
+        assert_eq!(errs.field_errors()["age"][0].code, "range");
+    }
+
+    #[cfg(feature = "validated_deserialize")]
+    #[test]
+    fn test_validated_serde() {
+        let age: Validated<Age> = serde_json::from_str("20").unwrap();
+        assert_eq!((*age).0, 20);
+        assert_eq!(serde_json::to_string(&age).unwrap(), "20");
+
+        assert!(serde_json::from_str::<Validated<Age>>("10").is_err());
+    }
+}
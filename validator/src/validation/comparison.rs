@@ -0,0 +1,94 @@
+/// Validates that the first value is strictly greater than the second one.
+/// The values can be of different types as long as they can be compared
+#[must_use]
+pub fn validate_greater_than<T, U>(a: &T, b: &U) -> bool
+where
+    T: PartialOrd<U> + ?Sized,
+    U: ?Sized,
+{
+    a > b
+}
+
+/// Validates that the first value is greater than or equal to the second one
+#[must_use]
+pub fn validate_greater_or_equal<T, U>(a: &T, b: &U) -> bool
+where
+    T: PartialOrd<U> + ?Sized,
+    U: ?Sized,
+{
+    a >= b
+}
+
+/// Validates that the first value is strictly less than the second one
+#[must_use]
+pub fn validate_less_than<T, U>(a: &T, b: &U) -> bool
+where
+    T: PartialOrd<U> + ?Sized,
+    U: ?Sized,
+{
+    a < b
+}
+
+/// Validates that the first value is less than or equal to the second one
+#[must_use]
+pub fn validate_less_or_equal<T, U>(a: &T, b: &U) -> bool
+where
+    T: PartialOrd<U> + ?Sized,
+    U: ?Sized,
+{
+    a <= b
+}
+
+/// Validates that the 2 values are different
+#[must_use]
+pub fn validate_not_equal<T, U>(a: &T, b: &U) -> bool
+where
+    T: PartialEq<U> + ?Sized,
+    U: ?Sized,
+{
+    a != b
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_greater_than() {
+        assert!(validate_greater_than(&3, &2));
+        assert!(!validate_greater_than(&2, &2));
+        assert!(!validate_greater_than(&1, &2));
+    }
+
+    #[test]
+    fn test_validate_greater_or_equal() {
+        assert!(validate_greater_or_equal(&3, &2));
+        assert!(validate_greater_or_equal(&2, &2));
+        assert!(!validate_greater_or_equal(&1, &2));
+    }
+
+    #[test]
+    fn test_validate_less_than() {
+        assert!(validate_less_than("a", "b"));
+        assert!(!validate_less_than("b", "b"));
+    }
+
+    #[test]
+    fn test_validate_less_or_equal() {
+        assert!(validate_less_or_equal(&1.5, &2.0));
+        assert!(validate_less_or_equal(&2.0, &2.0));
+        assert!(!validate_less_or_equal(&2.5, &2.0));
+    }
+
+    #[test]
+    fn test_validate_not_equal_different_types() {
+        assert!(validate_not_equal(&"hey".to_string(), "ho"));
+        assert!(!validate_not_equal(&"hey".to_string(), "hey"));
+    }
+
+    #[test]
+    fn test_validate_nan_is_never_valid() {
+        assert!(!validate_greater_than(&f64::NAN, &1.0));
+        assert!(!validate_less_or_equal(&f64::NAN, &1.0));
+    }
+}
@@ -1,5 +1,6 @@
 #[cfg(feature = "card")]
 pub mod cards;
+pub mod comparison;
 pub mod contains;
 pub mod email;
 pub mod ip;
@@ -7,10 +8,12 @@ pub mod length;
 pub mod must_match;
 #[cfg(feature = "unic")]
 pub mod non_control_character;
+pub mod not_blank;
+pub mod not_empty;
 #[cfg(feature = "phone")]
 pub mod phone;
 pub mod range;
 pub mod required;
 pub mod urls;
 
-pub use validator_types::Validator;
+pub use validator_types::{Validator, ValueOrPath};
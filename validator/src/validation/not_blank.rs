@@ -0,0 +1,45 @@
+use std::borrow::Cow;
+
+/// Validates that the string has at least one character that isn't whitespace,
+/// as defined by the Unicode `White_Space` property
+#[must_use]
+pub fn validate_not_blank<'a, T>(val: T) -> bool
+where
+    T: Into<Cow<'a, str>>,
+{
+    !val.into().chars().all(char::is_whitespace)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use super::validate_not_blank;
+
+    #[test]
+    fn test_validate_not_blank() {
+        let tests = vec![
+            ("hello", true),
+            ("  hello  ", true),
+            ("", false),
+            ("   ", false),
+            ("\t\r\n", false),
+            // No-break space and ideographic space
+            ("\u{00A0}\u{3000}", false),
+            // Zero width space isn't whitespace for Unicode
+            ("\u{200B}", true),
+        ];
+
+        for (input, expected) in tests {
+            assert_eq!(validate_not_blank(input), expected, "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn test_validate_not_blank_cow() {
+        let test: Cow<'static, str> = " a ".into();
+        assert!(validate_not_blank(test));
+        let test: Cow<'static, str> = String::from(" ").into();
+        assert!(!validate_not_blank(test));
+    }
+}
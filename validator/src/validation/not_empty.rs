@@ -0,0 +1,29 @@
+use crate::traits::HasLen;
+
+/// Validates that the value has a length greater than 0: a non empty string or collection
+#[must_use]
+pub fn validate_not_empty<T: HasLen>(value: T) -> bool {
+    value.length() > 0
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::validate_not_empty;
+
+    #[test]
+    fn test_validate_not_empty_strings() {
+        assert!(validate_not_empty("a"));
+        assert!(validate_not_empty(" "));
+        assert!(!validate_not_empty(""));
+        assert!(!validate_not_empty(String::new()));
+    }
+
+    #[test]
+    fn test_validate_not_empty_collections() {
+        assert!(validate_not_empty(vec![1]));
+        assert!(!validate_not_empty(Vec::<u8>::new()));
+        assert!(!validate_not_empty(HashMap::<u8, u8>::new()));
+    }
+}
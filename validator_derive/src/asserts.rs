@@ -1,121 +1,68 @@
-use regex::Regex;
+use std::collections::HashMap;
 
-use lazy_static::lazy_static;
 use proc_macro_error::abort;
 use syn::spanned::Spanned;
 
-lazy_static! {
-    pub static ref COW_TYPE: Regex = Regex::new(r"Cow<'[a-z]+,str>").unwrap();
-}
+use crate::types::{
+    is_known_non_number, is_known_non_string, is_known_without_len, strip_options, type_to_string,
+};
 
-pub static NUMBER_TYPES: [&str; 36] = [
-    "usize",
-    "u8",
-    "u16",
-    "u32",
-    "u64",
-    "isize",
-    "i8",
-    "i16",
-    "i32",
-    "i64",
-    "f32",
-    "f64",
-    "Option<usize>",
-    "Option<u8>",
-    "Option<u16>",
-    "Option<u32>",
-    "Option<u64>",
-    "Option<isize>",
-    "Option<i8>",
-    "Option<i16>",
-    "Option<i32>",
-    "Option<i64>",
-    "Option<f32>",
-    "Option<f64>",
-    "Option<Option<usize>>",
-    "Option<Option<u8>>",
-    "Option<Option<u16>>",
-    "Option<Option<u32>>",
-    "Option<Option<u64>>",
-    "Option<Option<isize>>",
-    "Option<Option<i8>>",
-    "Option<Option<i16>>",
-    "Option<Option<i32>>",
-    "Option<Option<i64>>",
-    "Option<Option<f32>>",
-    "Option<Option<f64>>",
-];
+// Those asserts only reject the types we know can't work with a validator to give a nicer
+// error message than the one of the compiler. Any other type is checked by the traits
+// the validators rely on.
 
-pub fn assert_string_type(name: &str, type_name: &str, field_type: &syn::Type) {
-    if type_name != "String"
-        && type_name != "&str"
-        && !COW_TYPE.is_match(type_name)
-        && type_name != "Option<String>"
-        && type_name != "Option<Option<String>>"
-        && !(type_name.starts_with("Option<") && type_name.ends_with("str>"))
-        && !(type_name.starts_with("Option<Option<") && type_name.ends_with("str>>"))
-    {
+pub fn assert_string_type(name: &str, field_type: &syn::Type) {
+    let (inner, _) = strip_options(field_type);
+    if is_known_non_string(inner) {
         abort!(
             field_type.span(),
-            "`{}` validator can only be used on String, &str, Cow<'_,str> or an Option of those",
+            "`{}` validator can only be used on String, &str, Cow<'_,str> or any other type implementing AsRef<str>, or an Option of those",
             name
         );
     }
 }
 
-pub fn assert_type_matches(
-    field_name: String,
-    field_type: &str,
-    field_type2: Option<&String>,
+pub fn assert_field_exists(
+    validator_name: &str,
+    field_name: &str,
+    other: &str,
+    field_types: &HashMap<String, syn::Type>,
     field_attr: &syn::Attribute,
 ) {
-    if let Some(t2) = field_type2 {
-        if field_type != t2 {
-            abort!(field_attr.span(), "Invalid argument for `must_match` validator of field `{}`: types of field can't match", field_name);
-        }
-    } else {
-        abort!(field_attr.span(), "Invalid argument for `must_match` validator of field `{}`: the other field doesn't exist in struct", field_name);
+    if !field_types.contains_key(other) {
+        abort!(field_attr.span(), "Invalid argument for `{}` validator of field `{}`: the other field doesn't exist in struct", validator_name, field_name);
     }
 }
 
-pub fn assert_has_len(field_name: String, type_name: &str, field_type: &syn::Type) {
-    if let syn::Type::Reference(ref tref) = field_type {
-        let elem = &tref.elem;
-        let type_name = format!("{}", quote::quote! { #elem }).replace(' ', "");
-
-        if type_name == "str" {
-            return;
-        }
-        assert_has_len(field_name, &type_name, elem);
-        return;
+pub fn assert_option_type(validator_name: &str, field_name: &str, field_type: &syn::Type) {
+    if strip_options(field_type).1 == 0 {
+        abort!(
+            field_type.span(),
+            "Validator `{}` can only be used on `Option` fields but found `{}` for field `{}`",
+            validator_name,
+            type_to_string(field_type),
+            field_name
+        );
     }
+}
 
-    if type_name != "String"
-        && !type_name.starts_with("Vec<")
-        && !type_name.starts_with("Option<Vec<")
-        && !type_name.starts_with("Option<Option<Vec<")
-        && type_name != "Option<String>"
-        && type_name != "Option<Option<String>>"
-        // a bit ugly
-        && !(type_name.starts_with("Option<") && type_name.ends_with("str>"))
-        && !(type_name.starts_with("Option<Option<") && type_name.ends_with("str>>"))
-        && !COW_TYPE.is_match(type_name)
-        && type_name != "&str"
-    {
+pub fn assert_has_len(field_name: &str, field_type: &syn::Type) {
+    let (inner, _) = strip_options(field_type);
+    if is_known_without_len(inner) {
         abort!(field_type.span(),
-                "Validator `length` can only be used on types `String`, `&str`, Cow<'_,str> or `Vec` but found `{}` for field `{}`",
-                type_name, field_name
+                "Validator `length` can only be used on types implementing HasLen like `String`, `&str`, Cow<'_,str> or `Vec` but found `{}` for field `{}`",
+                type_to_string(field_type), field_name
             );
     }
 }
 
-pub fn assert_has_range(field_name: String, type_name: &str, field_type: &syn::Type) {
-    if !NUMBER_TYPES.contains(&type_name) {
+pub fn assert_has_range(field_name: &str, field_type: &syn::Type) {
+    let (inner, _) = strip_options(field_type);
+    if is_known_non_number(inner) {
         abort!(
             field_type.span(),
             "Validator `range` can only be used on number types but found `{}` for field `{}`",
-            type_name,
+            type_to_string(field_type),
             field_name
         );
     }
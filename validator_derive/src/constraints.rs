@@ -0,0 +1,261 @@
+//! The `Constraints` impl generated alongside `Validate`, describing the declared validators.
+
+use quote::quote;
+use validator_types::{Validator, ValueOrPath};
+
+use crate::lit::option_to_tokens;
+use crate::validation::{FieldValidation, PresenceRule, PresenceValidation, SchemaValidation};
+
+#[derive(Default)]
+pub struct ConstraintsQuoter {
+    fields: Vec<proc_macro2::TokenStream>,
+}
+
+impl ConstraintsQuoter {
+    pub fn add_field(
+        &mut self,
+        name: &str,
+        validations: &[FieldValidation],
+        each_validations: &[FieldValidation],
+    ) {
+        let constraints = validations
+            .iter()
+            .map(|v| quote_constraint(v, false))
+            .chain(each_validations.iter().map(|v| quote_constraint(v, true)));
+
+        self.fields.push(quote!(
+            ::validator::FieldConstraints {
+                field: #name,
+                constraints: ::std::vec![#(#constraints),*],
+            }
+        ));
+    }
+
+    /// The struct level validations, under `__all__`
+    pub fn add_struct_validations(
+        &mut self,
+        schema_validations: &[SchemaValidation],
+        presence_validations: &[PresenceValidation],
+    ) {
+        if schema_validations.is_empty() && presence_validations.is_empty() {
+            return;
+        }
+
+        let schema_constraints = schema_validations.iter().map(|validation| {
+            let function = &validation.function;
+            let code = validation.code.as_deref().unwrap_or("custom");
+            let message = quote_message(&validation.message);
+            quote!(
+                ::validator::Constraint::new(
+                    ::validator::Validator::Custom(::std::string::String::from(#function)),
+                    #code,
+                    #message,
+                    false,
+                )
+            )
+        });
+        let presence_constraints = presence_validations.iter().map(|validation| {
+            let names = validation.fields.iter().map(|(_, name)| name).collect::<Vec<_>>();
+            let variant = match validation.rule {
+                PresenceRule::AtLeastOneOf => quote!(AtLeastOneOf),
+                PresenceRule::ExactlyOneOf => quote!(ExactlyOneOf),
+                PresenceRule::MutuallyExclusive => quote!(MutuallyExclusive),
+            };
+            let code = &validation.code;
+            let message = quote_message(&validation.message);
+            quote!({
+                let mut constraint = ::validator::Constraint::new(
+                    ::validator::Validator::#variant(
+                        ::std::vec![#(::std::string::String::from(#names)),*]
+                    ),
+                    #code,
+                    #message,
+                    false,
+                );
+                constraint.add_param(::std::borrow::Cow::from("fields"), &[#(#names),*]);
+                constraint
+            })
+        });
+        let constraints = schema_constraints.chain(presence_constraints);
+
+        self.fields.push(quote!(::validator::FieldConstraints {
+            field: "__all__",
+            constraints: ::std::vec![#(#constraints),*],
+        }));
+    }
+
+    pub fn quote_impl(self, ast: &syn::DeriveInput) -> proc_macro2::TokenStream {
+        let ident = &ast.ident;
+        let fields = &self.fields;
+        let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+
+        quote!(
+            impl #impl_generics ::validator::Constraints for #ident #ty_generics #where_clause {
+                fn constraints() -> ::std::vec::Vec<::validator::FieldConstraints> {
+                    ::std::vec![#(#fields),*]
+                }
+            }
+        )
+    }
+}
+
+fn quote_message(message: &Option<String>) -> proc_macro2::TokenStream {
+    option_to_tokens(&message.as_ref().map(|m| quote!(::std::borrow::Cow::from(#m))))
+}
+
+/// The `Constraint` of a validator, with the params its errors have apart from `value`
+fn quote_constraint(validation: &FieldValidation, each: bool) -> proc_macro2::TokenStream {
+    let validator = quote_validator(&validation.validator);
+    let code = &validation.code;
+    let message = quote_message(&validation.message);
+
+    let params = match validation.validator {
+        Validator::Length { ref min, ref max, ref equal } => {
+            quote_params(&[("min", min), ("max", max), ("equal", equal)])
+        }
+        Validator::Range { ref min, ref max } => quote_params(&[("min", min), ("max", max)]),
+        Validator::RequiredIf { ref other, ref value }
+        | Validator::RequiredUnless { ref other, ref value } => {
+            let value: syn::Expr = syn::parse_str(value).unwrap();
+            quote!(
+                constraint.add_param(::std::borrow::Cow::from("other"), &#other);
+                constraint.add_param(::std::borrow::Cow::from("value"), &#value);
+            )
+        }
+        Validator::MustMatch(ref other)
+        | Validator::RequiredWith(ref other)
+        | Validator::RequiredWithout(ref other)
+        | Validator::GreaterThan(ref other)
+        | Validator::GreaterOrEqual(ref other)
+        | Validator::LessThan(ref other)
+        | Validator::LessOrEqual(ref other)
+        | Validator::NotEqual(ref other) => {
+            quote!(constraint.add_param(::std::borrow::Cow::from("other"), &#other);)
+        }
+        Validator::Contains(ref needle) => {
+            quote!(constraint.add_param(::std::borrow::Cow::from("needle"), &#needle);)
+        }
+        Validator::Regex(ref re) => {
+            let re: syn::Path = syn::parse_str(re).unwrap();
+            quote!(constraint.add_param(::std::borrow::Cow::from("pattern"), &#re.as_str());)
+        }
+        Validator::Custom(_) if !validation.custom_args.is_empty() => {
+            let args = &validation.custom_args;
+            quote!(constraint.add_param(::std::borrow::Cow::from("args"), &(#(#args,)*));)
+        }
+        _ => quote!(),
+    };
+
+    quote!({
+        #[allow(unused_mut)]
+        let mut constraint = ::validator::Constraint::new(#validator, #code, #message, #each);
+        #params
+        constraint
+    })
+}
+
+fn quote_params<T>(params: &[(&str, &Option<ValueOrPath<T>>)]) -> proc_macro2::TokenStream
+where
+    T: std::fmt::Debug + Clone + PartialEq + quote::ToTokens,
+{
+    let params = params.iter().filter_map(|(name, value)| {
+        let value = match value.as_ref()? {
+            ValueOrPath::Value(ref v) => quote!(#v),
+            ValueOrPath::Path(ref path) => {
+                let path: syn::Path = syn::parse_str(path).unwrap();
+                quote!(#path)
+            }
+        };
+        Some(quote!(constraint.add_param(::std::borrow::Cow::from(#name), &#value);))
+    });
+    quote!(#(#params)*)
+}
+
+fn quote_value_or_path<T>(value: &Option<ValueOrPath<T>>) -> proc_macro2::TokenStream
+where
+    T: std::fmt::Debug + Clone + PartialEq + quote::ToTokens,
+{
+    option_to_tokens(&value.as_ref().map(|value| match value {
+        ValueOrPath::Value(ref v) => quote!(::validator::ValueOrPath::Value(#v)),
+        ValueOrPath::Path(ref path) => {
+            quote!(::validator::ValueOrPath::Path(::std::string::String::from(#path)))
+        }
+    }))
+}
+
+/// The tokens building the same `Validator` at runtime
+fn quote_validator(validator: &Validator) -> proc_macro2::TokenStream {
+    match *validator {
+        Validator::Email => quote!(::validator::Validator::Email),
+        Validator::Url => quote!(::validator::Validator::Url),
+        Validator::Custom(ref function) => {
+            quote!(::validator::Validator::Custom(::std::string::String::from(#function)))
+        }
+        Validator::MustMatch(ref other) => {
+            quote!(::validator::Validator::MustMatch(::std::string::String::from(#other)))
+        }
+        Validator::GreaterThan(ref other) => {
+            quote!(::validator::Validator::GreaterThan(::std::string::String::from(#other)))
+        }
+        Validator::GreaterOrEqual(ref other) => {
+            quote!(::validator::Validator::GreaterOrEqual(::std::string::String::from(#other)))
+        }
+        Validator::LessThan(ref other) => {
+            quote!(::validator::Validator::LessThan(::std::string::String::from(#other)))
+        }
+        Validator::LessOrEqual(ref other) => {
+            quote!(::validator::Validator::LessOrEqual(::std::string::String::from(#other)))
+        }
+        Validator::NotEqual(ref other) => {
+            quote!(::validator::Validator::NotEqual(::std::string::String::from(#other)))
+        }
+        Validator::Contains(ref needle) => {
+            quote!(::validator::Validator::Contains(::std::string::String::from(#needle)))
+        }
+        Validator::Regex(ref re) => {
+            quote!(::validator::Validator::Regex(::std::string::String::from(#re)))
+        }
+        Validator::Range { ref min, ref max } => {
+            let (min, max) = (quote_value_or_path(min), quote_value_or_path(max));
+            quote!(::validator::Validator::Range { min: #min, max: #max })
+        }
+        Validator::Length { ref min, ref max, ref equal } => {
+            let (min, max, equal) =
+                (quote_value_or_path(min), quote_value_or_path(max), quote_value_or_path(equal));
+            quote!(::validator::Validator::Length { min: #min, max: #max, equal: #equal })
+        }
+        #[cfg(feature = "card")]
+        Validator::CreditCard => quote!(::validator::Validator::CreditCard),
+        #[cfg(feature = "phone")]
+        Validator::Phone => quote!(::validator::Validator::Phone),
+        Validator::Nested => quote!(::validator::Validator::Nested),
+        #[cfg(feature = "unic")]
+        Validator::NonControlCharacter => quote!(::validator::Validator::NonControlCharacter),
+        Validator::Required => quote!(::validator::Validator::Required),
+        Validator::RequiredNested => quote!(::validator::Validator::RequiredNested),
+        Validator::NotBlank => quote!(::validator::Validator::NotBlank),
+        Validator::NotEmpty => quote!(::validator::Validator::NotEmpty),
+        Validator::RequiredIf { ref other, ref value } => quote!(
+            ::validator::Validator::RequiredIf {
+                other: ::std::string::String::from(#other),
+                value: ::std::string::String::from(#value),
+            }
+        ),
+        Validator::RequiredUnless { ref other, ref value } => quote!(
+            ::validator::Validator::RequiredUnless {
+                other: ::std::string::String::from(#other),
+                value: ::std::string::String::from(#value),
+            }
+        ),
+        Validator::RequiredWith(ref other) => {
+            quote!(::validator::Validator::RequiredWith(::std::string::String::from(#other)))
+        }
+        Validator::RequiredWithout(ref other) => {
+            quote!(::validator::Validator::RequiredWithout(::std::string::String::from(#other)))
+        }
+        // Only given on the struct, see `add_struct_validations`
+        Validator::AtLeastOneOf(_)
+        | Validator::ExactlyOneOf(_)
+        | Validator::MutuallyExclusive(_) => unreachable!(),
+    }
+}
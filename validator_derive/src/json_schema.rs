@@ -0,0 +1,144 @@
+//! The `ToJsonSchema` impl generated alongside `Validate` with the `json_schema` feature,
+//! describing the fields with the constraints of their validators.
+
+use quote::quote;
+use validator_types::Validator;
+
+use crate::lit::option_to_tokens;
+use crate::types::{
+    find_element_type, find_nested_type, is_vec, json_type, strip_options, uses_type_params,
+};
+use crate::validation::FieldValidation;
+
+#[derive(Default)]
+pub struct JsonSchemaQuoter {
+    properties: Vec<proc_macro2::TokenStream>,
+    bounds: Vec<syn::WherePredicate>,
+}
+
+impl JsonSchemaQuoter {
+    pub fn add_field(
+        &mut self,
+        name: &str,
+        ty: &syn::Type,
+        validations: &[FieldValidation],
+        type_params: &[&syn::Ident],
+    ) {
+        let (inner, depth) = strip_options(ty);
+
+        let schema = if validations.iter().any(|v| v.validator == Validator::Nested) {
+            let nested_type = find_nested_type(ty);
+            if uses_type_params(nested_type, type_params) {
+                self.bounds.push(syn::parse_quote!(#nested_type: ::validator::ToJsonSchema));
+            }
+            let reference = quote!(
+                ::validator::json_schema::PropertySchema::reference::<#nested_type>(defs)
+            );
+            if is_vec(inner) {
+                quote!(::validator::json_schema::PropertySchema::new(Some("array"))
+                    .items(#reference.constraints(field, true)))
+            } else {
+                reference
+            }
+        } else {
+            quote_type_schema(inner, true)
+        };
+
+        let mut schema = quote!(#schema.constraints(field, false));
+        if depth > 0 {
+            schema = quote!(#schema.nullable());
+        }
+        let required = depth == 0 || validations.iter().any(|v| v.validator == Validator::Required);
+
+        self.properties.push(quote!({
+            let field = ::validator::json_schema::field_constraints(&constraints, #name);
+            schema.property(#name, #schema, #required);
+        }));
+    }
+
+    pub fn quote_impl(self, ast: &syn::DeriveInput) -> proc_macro2::TokenStream {
+        let ident = &ast.ident;
+        let properties = &self.properties;
+
+        // The name is the key of the schema in `$defs` so it needs to be different for
+        // each instantiation of a generic struct
+        let name = ast
+            .attrs
+            .iter()
+            .filter(|attr| attr.path.is_ident("validate"))
+            .filter_map(|attr| crate::find_struct_option(attr, "schema_name"))
+            .next()
+            .map(|lit| lit.value())
+            .unwrap_or_else(|| ident.to_string());
+        let type_params = ast.generics.type_params().map(|p| &p.ident).collect::<Vec<_>>();
+        let schema_name = if type_params.is_empty() {
+            quote!(::std::borrow::Cow::Borrowed(#name))
+        } else {
+            quote!(::std::borrow::Cow::Owned(::std::format!(
+                "{}<{}>",
+                #name,
+                [#(::std::any::type_name::<#type_params>()),*].join(",")
+            )))
+        };
+
+        let mut generics = ast.generics.clone();
+        if !self.bounds.is_empty() {
+            generics.make_where_clause().predicates.extend(self.bounds);
+        }
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+        quote!(
+            impl #impl_generics ::validator::ToJsonSchema for #ident #ty_generics #where_clause {
+                fn schema_name() -> ::std::borrow::Cow<'static, str> {
+                    #schema_name
+                }
+
+                #[allow(unused_variables)]
+                fn schema(
+                    defs: &mut ::validator::json_schema::Map<
+                        ::std::string::String,
+                        ::validator::json_schema::Value,
+                    >,
+                ) -> ::validator::json_schema::Value {
+                    let constraints = <Self as ::validator::Constraints>::constraints();
+                    let mut schema = ::validator::json_schema::ObjectSchema::new();
+                    #(#properties)*
+                    schema.constraints(::validator::json_schema::field_constraints(
+                        &constraints,
+                        "__all__",
+                    ));
+                    schema.into_value()
+                }
+            }
+        )
+    }
+}
+
+/// The schema for a type, the `each` constraints of the field applying to its elements
+/// if it is a collection and `each` is true
+fn quote_type_schema(ty: &syn::Type, each: bool) -> proc_macro2::TokenStream {
+    let (inner, depth) = strip_options(ty);
+    let schema = match find_element_type(inner) {
+        Some((element_type, is_map)) => {
+            let mut elements = quote_type_schema(element_type, false);
+            if each {
+                elements = quote!(#elements.constraints(field, true));
+            }
+            if is_map {
+                quote!(::validator::json_schema::PropertySchema::new(Some("object")).values(#elements))
+            } else {
+                quote!(::validator::json_schema::PropertySchema::new(Some("array")).items(#elements))
+            }
+        }
+        None => {
+            let json_type = option_to_tokens(&json_type(inner));
+            quote!(::validator::json_schema::PropertySchema::new(#json_type))
+        }
+    };
+
+    if depth > 0 {
+        quote!(#schema.nullable())
+    } else {
+        schema
+    }
+}
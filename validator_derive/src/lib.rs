@@ -9,13 +9,24 @@ use syn::{parse_quote, spanned::Spanned};
 use validator_types::Validator;
 
 mod asserts;
+mod constraints;
+#[cfg(feature = "json_schema")]
+mod json_schema;
 mod lit;
 mod quoting;
+mod sanitization;
+mod types;
 mod validation;
 
-use asserts::{assert_has_len, assert_has_range, assert_string_type, assert_type_matches};
+use asserts::{
+    assert_field_exists, assert_has_len, assert_has_range, assert_option_type, assert_string_type,
+};
 use lit::*;
-use quoting::{quote_field_validation, quote_schema_validations, FieldQuoter};
+use quoting::{
+    quote_each_validation, quote_field_validation, quote_presence_validation,
+    quote_schema_validations, FieldQuoter,
+};
+use types::{find_element_type, find_nested_type, strip_options, type_to_string, uses_type_params};
 use validation::*;
 
 #[proc_macro_derive(Validate, attributes(validate))]
@@ -25,6 +36,13 @@ pub fn derive_validation(input: proc_macro::TokenStream) -> proc_macro::TokenStr
     impl_validate(&ast).into()
 }
 
+#[proc_macro_derive(Sanitize, attributes(sanitizer))]
+#[proc_macro_error]
+pub fn derive_sanitization(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let ast = syn::parse(input).unwrap();
+    sanitization::impl_sanitize(&ast).into()
+}
+
 fn impl_validate(ast: &syn::DeriveInput) -> proc_macro2::TokenStream {
     // Ensure the macro is on a struct with named fields
     let fields = match ast.data {
@@ -43,14 +61,27 @@ fn impl_validate(ast: &syn::DeriveInput) -> proc_macro2::TokenStream {
 
     let mut validations = vec![];
     let mut nested_validations = vec![];
+    // The same validations for `validate_fast`
+    let mut fast_validations = vec![];
+    let mut fast_nested_validations = vec![];
 
     let field_types = find_fields_type(&fields);
+    // The names of the fields in the errors, for the struct level validations
+    let mut field_names = HashMap::new();
+    let type_params = ast.generics.type_params().map(|param| &param.ident).collect::<Vec<_>>();
+    let mut inferred_bounds: Vec<syn::WherePredicate> = vec![];
+    let mut constraints = constraints::ConstraintsQuoter::default();
+    #[cfg(feature = "json_schema")]
+    let mut json_schema = json_schema::JsonSchemaQuoter::default();
 
     for field in &fields {
         let field_ident = field.ident.clone().unwrap();
-        let (name, field_validations) = find_validators_for_field(field, &field_types);
-        let field_type = field_types.get(&field_ident.to_string()).cloned().unwrap();
-        let field_quoter = FieldQuoter::new(field_ident, name, field_type);
+        let (name, field_validations, each_validations) =
+            find_validators_for_field(field, &field_types);
+        field_names.insert(field_ident.to_string(), name.clone());
+        let field_quoter = FieldQuoter::new(field_ident.clone(), name.clone(), field.ty.clone());
+        let fast_quoter =
+            FieldQuoter::new(field_ident.clone(), name.clone(), field.ty.clone()).fail_fast();
 
         for validation in &field_validations {
             quote_field_validation(
@@ -59,15 +90,82 @@ fn impl_validate(ast: &syn::DeriveInput) -> proc_macro2::TokenStream {
                 &mut validations,
                 &mut nested_validations,
             );
+            quote_field_validation(
+                &fast_quoter,
+                validation,
+                &mut fast_validations,
+                &mut fast_nested_validations,
+            );
+        }
+
+        // Nested structs generic over the struct type parameters need to implement `Validate`
+        if field_validations.iter().any(|v| v.validator == Validator::Nested) {
+            let nested_type = find_nested_type(&field.ty);
+            if uses_type_params(nested_type, &type_params) {
+                inferred_bounds.push(parse_quote!(#nested_type: ::validator::Validate));
+            }
+        }
+
+        constraints.add_field(&name, &field_validations, &each_validations);
+        #[cfg(feature = "json_schema")]
+        json_schema.add_field(&name, &field.ty, &field_validations, &type_params);
+
+        if !each_validations.is_empty() {
+            let (element_type, is_map) = find_element_type(&field.ty).unwrap();
+            let element_quoter =
+                FieldQuoter::new_element(field_ident.clone(), element_type.clone());
+            let fast_element_quoter =
+                FieldQuoter::new_element(field_ident, element_type.clone()).fail_fast();
+            let mut element_validations = vec![];
+            let mut fast_element_validations = vec![];
+            for validation in &each_validations {
+                quote_field_validation(
+                    &element_quoter,
+                    validation,
+                    &mut element_validations,
+                    &mut vec![],
+                );
+                quote_field_validation(
+                    &fast_element_quoter,
+                    validation,
+                    &mut fast_element_validations,
+                    &mut vec![],
+                );
+            }
+            nested_validations.push(quote_each_validation(
+                &field_quoter,
+                &element_quoter,
+                &element_validations,
+                is_map,
+            ));
+            fast_nested_validations.push(quote_each_validation(
+                &fast_quoter,
+                &fast_element_quoter,
+                &fast_element_validations,
+                is_map,
+            ));
         }
     }
 
-    let schema_validations = quote_schema_validations(&find_struct_validations(&ast.attrs));
+    let (struct_validations, presence_validations) =
+        find_struct_validations(&ast.attrs, &field_types, &field_names);
+    for validation in &presence_validations {
+        validations.push(quote_presence_validation(validation));
+        fast_validations.push(quote_presence_validation(validation));
+    }
+    constraints.add_struct_validations(&struct_validations, &presence_validations);
+    let schema_validations = quote_schema_validations(&struct_validations);
 
     let ident = &ast.ident;
 
+    let mut generics = ast.generics.clone();
+    let bounds = find_struct_bound(&ast.attrs, "validate").unwrap_or(inferred_bounds);
+    if !bounds.is_empty() {
+        generics.make_where_clause().predicates.extend(bounds);
+    }
+
     // Helper is provided for handling complex generic types correctly and effortlessly
-    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
     let impl_ast = quote!(
         impl #impl_generics ::validator::Validate for #ident #ty_generics #where_clause {
             #[allow(unused_mut)]
@@ -87,14 +185,54 @@ fn impl_validate(ast: &syn::DeriveInput) -> proc_macro2::TokenStream {
                 #(#nested_validations)*
                 result
             }
+
+            #[allow(unused_mut, clippy::question_mark)]
+            fn validate_fast(&self) -> ::std::result::Result<(), ::validator::ValidationErrors> {
+                let mut errors = ::validator::ValidationErrors::new();
+
+                #(
+                    #fast_validations
+                    if !errors.is_empty() {
+                        return ::std::result::Result::Err(errors);
+                    }
+                )*
+
+                #(
+                    #schema_validations
+                    if !errors.is_empty() {
+                        return ::std::result::Result::Err(errors);
+                    }
+                )*
+
+                let mut result = ::std::result::Result::Ok(());
+                #(
+                    #fast_nested_validations
+                    if result.is_err() {
+                        return result;
+                    }
+                )*
+                result
+            }
         }
     );
+    let constraints_impl = constraints.quote_impl(ast);
+    let impl_ast = quote!(#impl_ast #constraints_impl);
+    #[cfg(feature = "json_schema")]
+    let impl_ast = {
+        let json_schema_impl = json_schema.quote_impl(ast);
+        quote!(#impl_ast #json_schema_impl)
+    };
+
     // println!("{}", impl_ast.to_string());
     impl_ast
 }
 
-/// Find if a struct has some schema validation and returns the info if so
-fn find_struct_validation(attr: &syn::Attribute) -> SchemaValidation {
+/// Find if a struct has some schema validation or presence rule and returns the info if so
+fn find_struct_validation(
+    attr: &syn::Attribute,
+    field_types: &HashMap<String, syn::Type>,
+    field_names: &HashMap<String, String>,
+) -> StructValidation {
     let error = |span: Span, msg: &str| -> ! {
         abort!(span, "Invalid schema level validation: {}", msg);
     };
@@ -106,8 +244,21 @@ fn find_struct_validation(attr: &syn::Attribute) -> SchemaValidation {
 
         then {
             let ident = path.get_ident().unwrap();
+            if let Some(rule) = PresenceRule::from_name(&ident.to_string()) {
+                let meta_items = nested.iter().cloned().collect::<Vec<_>>();
+                return StructValidation::Presence(extract_presence_validation(
+                    rule,
+                    path,
+                    &meta_items,
+                    field_types,
+                    field_names,
+                ));
+            }
             if ident != "schema" {
-                error(attr.span(), "Only `schema` is allowed as validator on a struct")
+                error(
+                    attr.span(),
+                    "Only `schema`, `at_least_one_of`, `exactly_one_of` and `mutually_exclusive` are allowed as validators on a struct",
+                )
             }
 
             let mut function = String::new();
@@ -163,80 +314,109 @@ fn find_struct_validation(attr: &syn::Attribute) -> SchemaValidation {
                 error(path.span(), "`function` is required");
             }
 
-            SchemaValidation {
+            StructValidation::Schema(SchemaValidation {
                 function,
                 skip_on_field_errors,
                 code,
                 message,
-            }
+            })
         } else {
             error(attr.span(), "Unexpected struct validator")
         }
     }
 }
 
-/// Finds all struct schema validations
-fn find_struct_validations(struct_attrs: &[syn::Attribute]) -> Vec<SchemaValidation> {
-    struct_attrs
-        .iter()
-        .filter(|attribute| attribute.path == parse_quote!(validate))
-        .map(|attribute| find_struct_validation(attribute))
-        .collect()
+/// Finds all struct schema validations and presence rules
+fn find_struct_validations(
+    struct_attrs: &[syn::Attribute],
+    field_types: &HashMap<String, syn::Type>,
+    field_names: &HashMap<String, String>,
+) -> (Vec<SchemaValidation>, Vec<PresenceValidation>) {
+    let mut schema_validations = vec![];
+    let mut presence_validations = vec![];
+
+    for attr in struct_attrs {
+        if attr.path != parse_quote!(validate)
+            || find_bound(attr).is_some()
+            || find_struct_option(attr, "schema_name").is_some()
+        {
+            continue;
+        }
+        match find_struct_validation(attr, field_types, field_names) {
+            StructValidation::Schema(validation) => schema_validations.push(validation),
+            StructValidation::Presence(validation) => presence_validations.push(validation),
+        }
+    }
+
+    (schema_validations, presence_validations)
 }
 
-/// Find the types (as string) for each field of the struct
-/// Needed for the `must_match` filter
-fn find_fields_type(fields: &[syn::Field]) -> HashMap<String, String> {
-    let mut types = HashMap::new();
-
-    for field in fields {
-        let field_ident = field.ident.clone().unwrap().to_string();
-        let field_type = match field.ty {
-            syn::Type::Path(syn::TypePath { ref path, .. }) => {
-                let mut tokens = proc_macro2::TokenStream::new();
-                path.to_tokens(&mut tokens);
-                tokens.to_string().replace(' ', "")
-            }
-            syn::Type::Reference(syn::TypeReference { ref lifetime, ref elem, .. }) => {
-                let mut tokens = proc_macro2::TokenStream::new();
-                elem.to_tokens(&mut tokens);
-                let mut name = tokens.to_string().replace(' ', "");
-                if lifetime.is_some() {
-                    name.insert(0, '&')
-                }
-                name
-            }
-            syn::Type::Group(syn::TypeGroup { ref elem, .. }) => {
-                let mut tokens = proc_macro2::TokenStream::new();
-                elem.to_tokens(&mut tokens);
-                tokens.to_string().replace(' ', "")
+/// Returns the string given to a `#[validate(bound = "...")]` or `#[sanitizer(bound = "...")]` attribute
+fn find_bound(attr: &syn::Attribute) -> Option<syn::LitStr> {
+    find_struct_option(attr, "bound")
+}
+
+/// Returns the string given to a struct level option that needs its own attribute,
+/// like `#[validate(bound = "...")]`
+fn find_struct_option(attr: &syn::Attribute, name: &str) -> Option<syn::LitStr> {
+    if_chain! {
+        if let Ok(syn::Meta::List(syn::MetaList { ref nested, .. })) = attr.parse_meta();
+        if let Some(syn::NestedMeta::Meta(syn::Meta::NameValue(ref item))) = nested.first();
+        if item.path.is_ident(name);
+        then {
+            if nested.len() > 1 {
+                abort!(attr.span(), "Invalid struct level attribute: `{}` needs its own attribute", name);
             }
-            _ => {
-                let mut field_type = proc_macro2::TokenStream::new();
-                field.ty.to_tokens(&mut field_type);
-                abort!(
-                    field.ty.span(),
-                    "Type `{}` of field `{}` not supported",
-                    field_type,
-                    field_ident
-                )
+            match item.lit {
+                syn::Lit::Str(ref s) => Some(s.clone()),
+                _ => abort!(item.lit.span(), "Invalid argument type for `{}`: only a string is allowed", name),
             }
-        };
+        } else {
+            None
+        }
+    }
+}
 
-        //println!("{:?}", field_type);
-        types.insert(field_ident, field_type);
+/// Finds the where predicates given with `#[validate(bound = "...")]` (or `#[sanitizer(...)]`).
+/// Like in serde, they replace the bounds the derive infers for the generic nested fields.
+fn find_struct_bound(
+    struct_attrs: &[syn::Attribute],
+    attr_name: &str,
+) -> Option<Vec<syn::WherePredicate>> {
+    let mut bounds: Option<Vec<syn::WherePredicate>> = None;
+
+    for attr in struct_attrs.iter().filter(|attribute| attribute.path.is_ident(attr_name)) {
+        if let Some(lit) = find_bound(attr) {
+            let predicates = if lit.value().trim().is_empty() {
+                vec![]
+            } else {
+                match syn::parse_str::<syn::WhereClause>(&format!("where {}", lit.value())) {
+                    Ok(clause) => clause.predicates.into_iter().collect(),
+                    Err(e) => abort!(lit.span(), "Invalid `bound`: {}", e),
+                }
+            };
+            bounds.get_or_insert_with(Vec::new).extend(predicates);
+        }
     }
 
-    types
+    bounds
+}
+
+/// Find the types for each field of the struct
+/// Needed for the validators referring to other fields, like `must_match`
+fn find_fields_type(fields: &[syn::Field]) -> HashMap<String, syn::Type> {
+    fields
+        .iter()
+        .map(|field| (field.ident.clone().unwrap().to_string(), field.ty.clone()))
+        .collect()
 }
 
-/// Find everything we need to know about a field: its real name if it's changed from the serialization
-/// and the list of validators to run on it
+/// Find everything we need to know about a field: its real name if it's changed from the serialization,
+/// the list of validators to run on it and the list of validators to run on each of its elements
 fn find_validators_for_field(
     field: &syn::Field,
-    field_types: &HashMap<String, String>,
-) -> (String, Vec<FieldValidation>) {
-    let rust_ident = field.ident.clone().unwrap().to_string();
+    field_types: &HashMap<String, syn::Type>,
+) -> (String, Vec<FieldValidation>, Vec<FieldValidation>) {
     let mut field_ident = field.ident.clone().unwrap().to_string();
 
     let error = |span: Span, msg: &str| -> ! {
@@ -248,10 +428,10 @@ fn find_validators_for_field(
         );
     };
 
-    let field_type = field_types.get(&field_ident).unwrap();
-
     let mut validators = vec![];
+    let mut each_validators = vec![];
     let mut has_validate = false;
+    let mut sensitive = false;
 
     for attr in &field.attrs {
         if attr.path != parse_quote!(validate) && attr.path != parse_quote!(serde) {
@@ -276,171 +456,28 @@ fn find_validators_for_field(
                 // only validation from there on
                 for meta_item in meta_items {
                     match *meta_item {
-                        syn::NestedMeta::Meta(ref item) => match *item {
-                            // email, url, phone, credit_card, non_control_character
-                            syn::Meta::Path(ref name) => {
-                                match name.get_ident().unwrap().to_string().as_ref() {
-                                    "email" => {
-                                        assert_string_type("email", field_type, &field.ty);
-                                        validators.push(FieldValidation::new(Validator::Email));
-                                    }
-                                    "url" => {
-                                        assert_string_type("url", field_type, &field.ty);
-                                        validators.push(FieldValidation::new(Validator::Url));
-                                    }
-                                    #[cfg(feature = "phone")]
-                                    "phone" => {
-                                        assert_string_type("phone", field_type, &field.ty);
-                                        validators.push(FieldValidation::new(Validator::Phone));
-                                    }
-                                    #[cfg(feature = "card")]
-                                    "credit_card" => {
-                                        assert_string_type("credit_card", field_type, &field.ty);
-                                        validators
-                                            .push(FieldValidation::new(Validator::CreditCard));
-                                    }
-                                    #[cfg(feature = "unic")]
-                                    "non_control_character" => {
-                                        assert_string_type(
-                                            "non_control_character",
-                                            field_type,
-                                            &field.ty,
-                                        );
-                                        validators.push(FieldValidation::new(
-                                            Validator::NonControlCharacter,
-                                        ));
-                                    }
-                                    "required" => {
-                                        validators.push(FieldValidation::new(Validator::Required));
-                                    }
-                                    "required_nested" => {
-                                        validators.push(FieldValidation::new(Validator::Required));
-                                        validators.push(FieldValidation::new(Validator::Nested));
-                                    }
-                                    _ => {
-                                        let mut ident = proc_macro2::TokenStream::new();
-                                        name.to_tokens(&mut ident);
-                                        abort!(name.span(), "Unexpected validator: {}", ident)
-                                    }
-                                }
-                            }
-                            // custom, contains, must_match, regex
-                            syn::Meta::NameValue(syn::MetaNameValue {
-                                ref path, ref lit, ..
-                            }) => {
-                                let ident = path.get_ident().unwrap();
-                                match ident.to_string().as_ref() {
-                                    "custom" => {
-                                        match lit_to_string(lit) {
-                                            Some(s) => validators.push(FieldValidation::new(Validator::Custom(s))),
-                                            None => error(lit.span(), "invalid argument for `custom` validator: only strings are allowed"),
-                                        };
-                                    }
-                                    "contains" => {
-                                        match lit_to_string(lit) {
-                                            Some(s) => validators.push(FieldValidation::new(Validator::Contains(s))),
-                                            None => error(lit.span(), "invalid argument for `contains` validator: only strings are allowed"),
-                                        };
-                                    }
-                                    "regex" => {
-                                        match lit_to_string(lit) {
-                                            Some(s) => validators.push(FieldValidation::new(Validator::Regex(s))),
-                                            None => error(lit.span(), "invalid argument for `regex` validator: only strings are allowed"),
-                                        };
-                                    }
-                                    "must_match" => {
-                                        match lit_to_string(lit) {
-                                            Some(s) => {
-                                                assert_type_matches(rust_ident.clone(), field_type, field_types.get(&s), &attr);
-                                                validators.push(FieldValidation::new(Validator::MustMatch(s)));
-                                            },
-                                            None => error(lit.span(), "invalid argument for `must_match` validator: only strings are allowed"),
-                                        };
-                                    }
-                                    v => abort!(
-                                        path.span(),
-                                        "unexpected name value validator: {:?}",
-                                        v
-                                    ),
-                                };
-                            }
-                            // Validators with several args
-                            syn::Meta::List(syn::MetaList { ref path, ref nested, .. }) => {
-                                let meta_items = nested.iter().cloned().collect::<Vec<_>>();
-                                let ident = path.get_ident().unwrap();
-                                match ident.to_string().as_ref() {
-                                    "length" => {
-                                        assert_has_len(rust_ident.clone(), field_type, &field.ty);
-                                        validators.push(extract_length_validation(
-                                            rust_ident.clone(),
-                                            attr,
-                                            &meta_items,
-                                        ));
-                                    }
-                                    "range" => {
-                                        assert_has_range(rust_ident.clone(), field_type, &field.ty);
-                                        validators.push(extract_range_validation(
-                                            rust_ident.clone(),
-                                            attr,
-                                            &meta_items,
-                                        ));
-                                    }
-                                    "email"
-                                    | "url"
-                                    | "phone"
-                                    | "credit_card"
-                                    | "non_control_character" => {
-                                        validators.push(extract_argless_validation(
-                                            ident.to_string(),
-                                            rust_ident.clone(),
-                                            &meta_items,
-                                        ));
-                                    }
-                                    "custom" => {
-                                        validators.push(extract_one_arg_validation(
-                                            "function",
-                                            ident.to_string(),
-                                            rust_ident.clone(),
-                                            &meta_items,
-                                        ));
-                                    }
-                                    "contains" => {
-                                        validators.push(extract_one_arg_validation(
-                                            "pattern",
-                                            ident.to_string(),
-                                            rust_ident.clone(),
-                                            &meta_items,
-                                        ));
-                                    }
-                                    "regex" => {
-                                        validators.push(extract_one_arg_validation(
-                                            "path",
-                                            ident.to_string(),
-                                            rust_ident.clone(),
-                                            &meta_items,
-                                        ));
-                                    }
-                                    "must_match" => {
-                                        let validation = extract_one_arg_validation(
-                                            "other",
-                                            ident.to_string(),
-                                            rust_ident.clone(),
-                                            &meta_items,
-                                        );
-                                        if let Validator::MustMatch(ref t2) = validation.validator {
-                                            assert_type_matches(
-                                                rust_ident.clone(),
-                                                field_type,
-                                                field_types.get(t2),
-                                                &attr,
-                                            );
-                                        }
-                                        validators.push(validation);
-                                    }
-                                    v => abort!(path.span(), "unexpected list validator: {:?}", v),
-                                }
-                            }
-                        },
+                        syn::NestedMeta::Meta(syn::Meta::Path(ref path))
+                            if path.is_ident("sensitive") =>
+                        {
+                            sensitive = true;
+                        }
+                        syn::NestedMeta::Meta(syn::Meta::List(ref list))
+                            if list.path.is_ident("each") =>
+                        {
+                            each_validators.extend(find_each_validators(
+                                field,
+                                field_types,
+                                attr,
+                                list,
+                            ));
+                        }
+                        syn::NestedMeta::Meta(ref item) => validators.extend(find_validator(
+                            field,
+                            &field.ty,
+                            field_types,
+                            attr,
+                            item,
+                        )),
                         _ => unreachable!("Found a non Meta while looking for validators"),
                     };
                 }
@@ -453,12 +490,383 @@ fn find_validators_for_field(
             ),
         }
 
-        if has_validate && validators.is_empty() {
+        if has_validate && validators.is_empty() && each_validators.is_empty() {
             error(attr.span(), "it needs at least one validator");
         }
     }
 
-    (field_ident, validators)
+    if sensitive {
+        for validation in validators.iter_mut().chain(each_validators.iter_mut()) {
+            validation.sensitive = true;
+        }
+    }
+
+    (field_ident, validators, each_validators)
+}
+
+/// Parses a single validator of a `#[validate]` attribute, checking that it can be used
+/// on a value of type `ty`
+fn find_validator(
+    field: &syn::Field,
+    ty: &syn::Type,
+    field_types: &HashMap<String, syn::Type>,
+    attr: &syn::Attribute,
+    item: &syn::Meta,
+) -> Vec<FieldValidation> {
+    let rust_ident = field.ident.clone().unwrap().to_string();
+
+    let error = |span: Span, msg: &str| -> ! {
+        abort!(span, "Invalid attribute #[validate] on field `{}`: {}", rust_ident, msg);
+    };
+
+    let mut validators = vec![];
+
+    match *item {
+        // email, url, phone, credit_card, non_control_character
+        syn::Meta::Path(ref name) => match name.get_ident().unwrap().to_string().as_ref() {
+            "email" => {
+                assert_string_type("email", ty);
+                validators.push(FieldValidation::new(Validator::Email));
+            }
+            "url" => {
+                assert_string_type("url", ty);
+                validators.push(FieldValidation::new(Validator::Url));
+            }
+            #[cfg(feature = "phone")]
+            "phone" => {
+                assert_string_type("phone", ty);
+                validators.push(FieldValidation::new(Validator::Phone));
+            }
+            #[cfg(feature = "card")]
+            "credit_card" => {
+                assert_string_type("credit_card", ty);
+                validators.push(FieldValidation::new(Validator::CreditCard));
+            }
+            #[cfg(feature = "unic")]
+            "non_control_character" => {
+                assert_string_type("non_control_character", ty);
+                validators.push(FieldValidation::new(Validator::NonControlCharacter));
+            }
+            "not_blank" => {
+                assert_string_type("not_blank", ty);
+                validators.push(FieldValidation::new(Validator::NotBlank));
+            }
+            "not_empty" => {
+                assert_has_len(&rust_ident, ty);
+                validators.push(FieldValidation::new(Validator::NotEmpty));
+            }
+            "nested" => {
+                validators.push(FieldValidation::new(Validator::Nested));
+            }
+            "required" => {
+                validators.push(FieldValidation::new(Validator::Required));
+            }
+            "required_nested" => {
+                validators.push(FieldValidation::new(Validator::Required));
+                validators.push(FieldValidation::new(Validator::Nested));
+            }
+            _ => {
+                let mut ident = proc_macro2::TokenStream::new();
+                name.to_tokens(&mut ident);
+                abort!(name.span(), "Unexpected validator: {}", ident)
+            }
+        },
+        // custom, contains, must_match, regex
+        syn::Meta::NameValue(syn::MetaNameValue { ref path, ref lit, .. }) => {
+            let ident = path.get_ident().unwrap();
+            match ident.to_string().as_ref() {
+                "custom" => {
+                    match lit_to_string(lit) {
+                        Some(s) => validators.push(FieldValidation::new(Validator::Custom(s))),
+                        None => error(
+                            lit.span(),
+                            "invalid argument for `custom` validator: only strings are allowed",
+                        ),
+                    };
+                }
+                "contains" => {
+                    match lit_to_string(lit) {
+                        Some(s) => validators.push(FieldValidation::new(Validator::Contains(s))),
+                        None => error(
+                            lit.span(),
+                            "invalid argument for `contains` validator: only strings are allowed",
+                        ),
+                    };
+                }
+                "regex" => {
+                    match lit_to_string(lit) {
+                        Some(s) => validators.push(FieldValidation::new(Validator::Regex(s))),
+                        None => error(
+                            lit.span(),
+                            "invalid argument for `regex` validator: only strings are allowed",
+                        ),
+                    };
+                }
+                "must_match" => {
+                    match lit_to_string(lit) {
+                        Some(s) => {
+                            assert_field_exists("must_match", &rust_ident, &s, field_types, attr);
+                            validators.push(FieldValidation::new(Validator::MustMatch(s)));
+                        }
+                        None => error(
+                            lit.span(),
+                            "invalid argument for `must_match` validator: only strings are allowed",
+                        ),
+                    };
+                }
+                v @ "required_with" | v @ "required_without" => {
+                    assert_option_type(v, &rust_ident, ty);
+                    match lit_to_string(lit) {
+                        Some(s) => {
+                            let validator = if v == "required_with" {
+                                Validator::RequiredWith(s)
+                            } else {
+                                Validator::RequiredWithout(s)
+                            };
+                            let validation = FieldValidation::new(validator);
+                            validators.push(with_other_field(
+                                validation,
+                                &rust_ident,
+                                field_types,
+                                attr,
+                            ));
+                        }
+                        None => error(
+                            lit.span(),
+                            &format!(
+                                "invalid argument for `{}` validator: only strings are allowed",
+                                v
+                            ),
+                        ),
+                    };
+                }
+                v if COMPARISONS.contains(&v) => {
+                    match lit_to_string(lit) {
+                        Some(s) => {
+                            let validation = FieldValidation::new(comparison_validator(v, s));
+                            validators.push(with_other_field(
+                                validation,
+                                &rust_ident,
+                                field_types,
+                                attr,
+                            ));
+                        }
+                        None => error(
+                            lit.span(),
+                            &format!(
+                                "invalid argument for `{}` validator: only strings are allowed",
+                                v
+                            ),
+                        ),
+                    };
+                }
+                v => abort!(path.span(), "unexpected name value validator: {:?}", v),
+            };
+        }
+        // Validators with several args
+        syn::Meta::List(syn::MetaList { ref path, ref nested, .. }) => {
+            let meta_items = nested.iter().cloned().collect::<Vec<_>>();
+            let ident = path.get_ident().unwrap();
+            match ident.to_string().as_ref() {
+                "length" => {
+                    assert_has_len(&rust_ident, ty);
+                    validators.push(extract_length_validation(
+                        rust_ident.clone(),
+                        attr,
+                        &meta_items,
+                    ));
+                }
+                "range" => {
+                    assert_has_range(&rust_ident, ty);
+                    validators.push(extract_range_validation(
+                        rust_ident.clone(),
+                        attr,
+                        &meta_items,
+                    ));
+                }
+                "not_blank" => {
+                    assert_string_type("not_blank", ty);
+                    validators.push(extract_argless_validation(
+                        ident.to_string(),
+                        rust_ident.clone(),
+                        &meta_items,
+                    ));
+                }
+                "not_empty" => {
+                    assert_has_len(&rust_ident, ty);
+                    validators.push(extract_argless_validation(
+                        ident.to_string(),
+                        rust_ident.clone(),
+                        &meta_items,
+                    ));
+                }
+                "email" | "url" | "phone" | "credit_card" | "non_control_character" => {
+                    validators.push(extract_argless_validation(
+                        ident.to_string(),
+                        rust_ident.clone(),
+                        &meta_items,
+                    ));
+                }
+                "custom" => {
+                    validators.push(extract_custom_validation(rust_ident.clone(), &meta_items));
+                }
+                "contains" => {
+                    validators.push(extract_one_arg_validation(
+                        "pattern",
+                        ident.to_string(),
+                        rust_ident.clone(),
+                        &meta_items,
+                    ));
+                }
+                "regex" => {
+                    validators.push(extract_one_arg_validation(
+                        "path",
+                        ident.to_string(),
+                        rust_ident.clone(),
+                        &meta_items,
+                    ));
+                }
+                "must_match" => {
+                    let validation = extract_one_arg_validation(
+                        "other",
+                        ident.to_string(),
+                        rust_ident.clone(),
+                        &meta_items,
+                    );
+                    if let Validator::MustMatch(ref t2) = validation.validator {
+                        assert_field_exists("must_match", &rust_ident, t2, field_types, attr);
+                    }
+                    validators.push(validation);
+                }
+                v @ "required_with" | v @ "required_without" => {
+                    assert_option_type(v, &rust_ident, ty);
+                    let validation = extract_one_arg_validation(
+                        "field",
+                        ident.to_string(),
+                        rust_ident.clone(),
+                        &meta_items,
+                    );
+                    validators.push(with_other_field(validation, &rust_ident, field_types, attr));
+                }
+                v @ "required_if" | v @ "required_unless" => {
+                    assert_option_type(v, &rust_ident, ty);
+                    let validation = extract_required_if_validation(
+                        ident.to_string(),
+                        rust_ident.clone(),
+                        &meta_items,
+                    );
+                    validators.push(with_other_field(validation, &rust_ident, field_types, attr));
+                }
+                v if COMPARISONS.contains(&v) => {
+                    let validation = extract_one_arg_validation(
+                        "other",
+                        ident.to_string(),
+                        rust_ident.clone(),
+                        &meta_items,
+                    );
+                    validators.push(with_other_field(validation, &rust_ident, field_types, attr));
+                }
+                v => abort!(path.span(), "unexpected list validator: {:?}", v),
+            }
+        }
+    }
+
+    validators
+}
+
+/// Checks the field a comparison or a conditional `required_*` refers to exists and finds
+/// how many `Option`s wrap it
+fn with_other_field(
+    mut validation: FieldValidation,
+    field_name: &str,
+    field_types: &HashMap<String, syn::Type>,
+    attr: &syn::Attribute,
+) -> FieldValidation {
+    let other = match validation.validator {
+        Validator::GreaterThan(ref other)
+        | Validator::GreaterOrEqual(ref other)
+        | Validator::LessThan(ref other)
+        | Validator::LessOrEqual(ref other)
+        | Validator::NotEqual(ref other)
+        | Validator::RequiredIf { ref other, .. }
+        | Validator::RequiredUnless { ref other, .. }
+        | Validator::RequiredWith(ref other)
+        | Validator::RequiredWithout(ref other) => other,
+        _ => unreachable!(),
+    };
+    assert_field_exists(validation.validator.code(), field_name, other, field_types, attr);
+    // Only an `Option` can have a value or not
+    if let Validator::RequiredWith(_) | Validator::RequiredWithout(_) = validation.validator {
+        if strip_options(&field_types[other]).1 == 0 {
+            abort!(
+                field_types[other].span(),
+                "Invalid argument for `{}` validator of field `{}`: the other field `{}` must be an `Option`",
+                validation.validator.code(),
+                field_name,
+                other
+            );
+        }
+    }
+    validation.other_options = strip_options(&field_types[other]).1;
+    validation
+}
+
+/// Parses the validators given to `each`, which are run on every element of the collection
+fn find_each_validators(
+    field: &syn::Field,
+    field_types: &HashMap<String, syn::Type>,
+    attr: &syn::Attribute,
+    list: &syn::MetaList,
+) -> Vec<FieldValidation> {
+    let (element_type, _) = match find_element_type(&field.ty) {
+        Some(e) => e,
+        None => abort!(
+            field.ty.span(),
+            "Validator `each` can only be used on a Vec, slice, set or map (or an Option of those) but found `{}` for field `{}`",
+            type_to_string(&field.ty),
+            field.ident.clone().unwrap()
+        ),
+    };
+
+    let mut validators = vec![];
+    for meta_item in &list.nested {
+        match *meta_item {
+            syn::NestedMeta::Meta(ref item) => {
+                if item.path().is_ident("each") {
+                    abort!(item.span(), "Validator `each` can't be nested");
+                }
+                validators.extend(find_validator(field, element_type, field_types, attr, item));
+            }
+            _ => abort!(meta_item.span(), "Unexpected literal in `each` validator"),
+        }
+    }
+
+    if validators.is_empty() {
+        abort!(list.span(), "Validator `each` requires at least one validator");
+    }
+
+    for validation in &validators {
+        match validation.validator {
+            Validator::MustMatch(_)
+            | Validator::GreaterThan(_)
+            | Validator::GreaterOrEqual(_)
+            | Validator::LessThan(_)
+            | Validator::LessOrEqual(_)
+            | Validator::NotEqual(_)
+            | Validator::RequiredIf { .. }
+            | Validator::RequiredUnless { .. }
+            | Validator::RequiredWith(_)
+            | Validator::RequiredWithout(_)
+            | Validator::Nested => abort!(
+                list.span(),
+                "Validator `{}` can't be used in `each`",
+                validation.validator.code()
+            ),
+            _ => (),
+        }
+    }
+
+    validators
 }
 
 /// Serde can be used to rename fields on deserialization but most of the times
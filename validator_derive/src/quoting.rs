@@ -1,10 +1,10 @@
 use proc_macro2::{self, Span};
-use quote::quote;
+use quote::{quote, quote_spanned};
 use validator_types::Validator;
 
-use crate::asserts::{COW_TYPE, NUMBER_TYPES};
 use crate::lit::{option_to_tokens, value_or_path_to_tokens};
-use crate::validation::{FieldValidation, SchemaValidation};
+use crate::types::{is_cow, is_number, is_reference, is_vec, strip_options};
+use crate::validation::{FieldValidation, PresenceRule, PresenceValidation, SchemaValidation};
 
 /// Pass around all the information needed for creating a validation
 #[derive(Debug)]
@@ -13,106 +13,215 @@ pub struct FieldQuoter {
     /// The field name
     name: String,
     /// The field type
-    _type: String,
+    _type: syn::Type,
+    /// Whether this quotes an element of the field rather than the field itself, in
+    /// which case `ident` is a reference to the element
+    element: bool,
+    /// Whether this quotes the validations of `validate_fast`, which stop at the first error
+    fail_fast: bool,
 }
 
 impl FieldQuoter {
-    pub fn new(ident: syn::Ident, name: String, _type: String) -> FieldQuoter {
-        FieldQuoter { ident, name, _type }
+    pub fn new(ident: syn::Ident, name: String, _type: syn::Type) -> FieldQuoter {
+        FieldQuoter { ident, name, _type, element: false, fail_fast: false }
     }
 
-    /// Don't put a & in front a pointer since we are going to pass
-    /// a reference to the validator
-    /// Also just use the ident without if it's optional and will go through
-    /// a if let first
-    pub fn quote_validator_param(&self) -> proc_macro2::TokenStream {
+    /// Quotes the elements of a collection field, one at a time, for `each`.
+    /// The errors of an element are about the element itself so they go under `__all__`.
+    pub fn new_element(ident: syn::Ident, _type: syn::Type) -> FieldQuoter {
+        FieldQuoter { ident, name: "__all__".to_string(), _type, element: true, fail_fast: false }
+    }
+
+    /// Quotes the validations for `validate_fast` instead of `validate`
+    pub fn fail_fast(mut self) -> FieldQuoter {
+        self.fail_fast = true;
+        self
+    }
+
+    /// The type of the value given to the validators, once out of its `Option`s
+    fn value_type(&self) -> &syn::Type {
+        strip_options(&self._type).0
+    }
+
+    /// Whether `ident` is bound to a reference to the value, either because we are
+    /// validating an element or because `wrap_if_option` matched the `Option`
+    fn is_bound(&self) -> bool {
+        self.element || strip_options(&self._type).1 > 0
+    }
+
+    /// Where the value lives, before going through `wrap_if_option`
+    fn quote_source(&self) -> proc_macro2::TokenStream {
         let ident = &self.ident;
+        if self.element {
+            quote!(*#ident)
+        } else {
+            quote!(self.#ident)
+        }
+    }
 
-        if self._type.starts_with("Option<") {
+    /// A reference to the value before going through `wrap_if_option`, for validators
+    /// taking the `Option` itself
+    pub fn quote_source_ref(&self) -> proc_macro2::TokenStream {
+        let ident = &self.ident;
+        if self.element {
             quote!(#ident)
-        } else if COW_TYPE.is_match(&self._type.as_ref()) {
-            quote!(self.#ident.as_ref())
-        } else if self._type.starts_with('&') || NUMBER_TYPES.contains(&self._type.as_ref()) {
-            quote!(self.#ident)
         } else {
             quote!(&self.#ident)
         }
     }
 
-    pub fn quote_validator_field(&self) -> proc_macro2::TokenStream {
+    /// The value to validate, for the types that are `Copy`
+    fn quote_place(&self) -> proc_macro2::TokenStream {
         let ident = &self.ident;
+        if self.is_bound() {
+            quote!(*#ident)
+        } else {
+            quote!(self.#ident)
+        }
+    }
 
-        if self._type.starts_with("Option<") || self._type.starts_with("Vec<") {
+    /// A reference to the value to validate
+    pub fn quote_validator_ref(&self) -> proc_macro2::TokenStream {
+        let ident = &self.ident;
+        if self.is_bound() {
             quote!(#ident)
-        } else if COW_TYPE.is_match(&self._type.as_ref()) {
-            quote!(self.#ident.as_ref())
         } else {
-            quote!(self.#ident)
+            quote!(&self.#ident)
         }
     }
 
-    pub fn get_optional_validator_param(&self) -> proc_macro2::TokenStream {
+    /// Don't put a & in front a pointer since we are going to pass
+    /// a reference to the validator
+    /// Numbers are passed by value and Cow are turned into a &str
+    pub fn quote_validator_param(&self) -> proc_macro2::TokenStream {
+        let ty = self.value_type();
+
+        if is_cow(ty) {
+            let validator_ref = self.quote_validator_ref();
+            quote!(#validator_ref.as_ref())
+        } else if is_reference(ty) || is_number(ty) {
+            self.quote_place()
+        } else {
+            self.quote_validator_ref()
+        }
+    }
+
+    /// The value as a &str, for the validators working on strings
+    pub fn quote_validator_str(&self) -> proc_macro2::TokenStream {
+        let validator_ref = self.quote_validator_ref();
+        quote!(::std::convert::AsRef::<str>::as_ref(#validator_ref))
+    }
+
+    /// The value as a number, for the `range` validator
+    pub fn quote_validator_number(&self) -> proc_macro2::TokenStream {
+        self.quote_place()
+    }
+
+    pub fn quote_validator_field(&self) -> proc_macro2::TokenStream {
         let ident = &self.ident;
-        if self._type.starts_with("Option<&")
-            || self._type.starts_with("Option<Option<&")
-            || NUMBER_TYPES.contains(&self._type.as_ref())
-        {
+
+        if self.is_bound() || is_vec(self.value_type()) {
             quote!(#ident)
         } else {
-            quote!(ref #ident)
+            quote!(self.#ident)
         }
     }
 
     /// Wrap the quoted output of a validation with a if let Some if
-    /// the field type is an option
+    /// the field type is an option, binding a reference to the value to `ident`
     pub fn wrap_if_option(&self, tokens: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
-        let field_ident = &self.ident;
-        let optional_pattern_matched = self.get_optional_validator_param();
-        if self._type.starts_with("Option<Option<") {
-            return quote!(
-                if let Some(Some(#optional_pattern_matched)) = self.#field_ident {
-                    #tokens
-                }
-            );
-        } else if self._type.starts_with("Option<") {
-            return quote!(
-                if let Some(#optional_pattern_matched) = self.#field_ident {
-                    #tokens
-                }
-            );
+        let ident = &self.ident;
+        let (_, depth) = strip_options(&self._type);
+        if depth == 0 {
+            return tokens;
         }
 
-        tokens
+        let mut pattern = quote!(ref #ident);
+        for _ in 0..depth {
+            pattern = quote!(Some(#pattern));
+        }
+        let source = self.quote_source();
+        quote!(
+            if let #pattern = #source {
+                #tokens
+            }
+        )
     }
 
     /// Wrap the quoted output of a validation with a for loop if
     /// the field type is a vector
     pub fn wrap_if_vector(&self, tokens: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        if !is_vec(self.value_type()) {
+            return tokens;
+        }
+
         let field_ident = &self.ident;
         let field_name = &self.name;
-        if self._type.starts_with("Vec<") {
-            return quote!(
-            if !::validator::ValidationErrors::has_error(&result, #field_name) {
-                let results: Vec<_> = self.#field_ident.iter().map(|#field_ident| {
-                    let mut result = ::std::result::Result::Ok(());
-                    #tokens
-                    result
-                }).collect();
-                result = ::validator::ValidationErrors::merge_all(result, #field_name, results);
-            });
-        } else if self._type.starts_with("Option<Vec<") {
-            return quote!(
-            if !::validator::ValidationErrors::has_error(&result, #field_name) {
-                let results: Vec<_> = #field_ident.iter().map(|#field_ident| {
-                    let mut result = ::std::result::Result::Ok(());
-                    #tokens
-                    result
-                }).collect();
-                result = ::validator::ValidationErrors::merge_all(result, #field_name, results);
-            });
-        }
-
-        tokens
+        let collection =
+            if self.is_bound() { quote!(#field_ident) } else { quote!(self.#field_ident) };
+        let results = self.quote_results(
+            quote!(#collection.iter()),
+            quote!({
+                let mut result = ::std::result::Result::Ok(());
+                #tokens
+                result
+            }),
+            false,
+        );
+        quote!(
+        if !::validator::ValidationErrors::has_error(&result, #field_name) {
+            #results
+            result = ::validator::ValidationErrors::merge_all(result, #field_name, results);
+        })
+    }
+
+    /// Collects the result of `body` for each element into `results`, stopping after
+    /// the first error with `fail_fast`. For maps, the results are paired with their key.
+    fn quote_results(
+        &self,
+        elements: proc_macro2::TokenStream,
+        body: proc_macro2::TokenStream,
+        is_map: bool,
+    ) -> proc_macro2::TokenStream {
+        let ident = &self.ident;
+        let (pattern, item) = if is_map {
+            (
+                quote!((__validator_key, #ident)),
+                quote!((::std::string::ToString::to_string(__validator_key), element_result)),
+            )
+        } else {
+            (quote!(#ident), quote!(element_result))
+        };
+        if self.fail_fast {
+            quote!(
+                let mut results = ::std::vec::Vec::new();
+                for #pattern in #elements {
+                    let element_result = #body;
+                    let failed = element_result.is_err();
+                    results.push(#item);
+                    if failed {
+                        break;
+                    }
+                }
+            )
+        } else {
+            quote!(let results: Vec<_> = #elements.map(|#pattern| {
+                let element_result = #body;
+                #item
+            }).collect();)
+        }
+    }
+}
+
+/// Adds the invalid value to `err`, redacted for the `sensitive` fields
+fn quote_value_param(
+    validation: &FieldValidation,
+    value: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    if validation.sensitive {
+        quote!(err.add_sensitive_value_param(&#value);)
+    } else {
+        quote!(err.add_value_param(&#value);)
     }
 }
 
@@ -136,7 +245,7 @@ pub fn quote_length_validation(
     validation: &FieldValidation,
 ) -> proc_macro2::TokenStream {
     let field_name = &field_quoter.name;
-    let validator_param = field_quoter.quote_validator_param();
+    let validator_param = field_quoter.quote_validator_ref();
 
     if let Validator::Length { min, max, equal } = &validation.validator {
         let min_err_param_quoted = if let Some(v) = min {
@@ -168,7 +277,8 @@ pub fn quote_length_validation(
             &equal.clone().map(|ref x| value_or_path_to_tokens(x)).map(|x| quote!(#x as u64)),
         );
 
-        let quoted_error = quote_error(&validation);
+        let quoted_error = quote_error(validation);
+        let value_param = quote_value_param(validation, quote!(#validator_param));
         let quoted = quote!(
             if !::validator::validate_length(
                 #validator_param,
@@ -180,7 +290,7 @@ pub fn quote_length_validation(
                 #min_err_param_quoted
                 #max_err_param_quoted
                 #equal_err_param_quoted
-                err.add_param(::std::borrow::Cow::from("value"), &#validator_param);
+                #value_param
                 errors.add(#field_name, err);
             }
         );
@@ -196,7 +306,7 @@ pub fn quote_range_validation(
     validation: &FieldValidation,
 ) -> proc_macro2::TokenStream {
     let field_name = &field_quoter.name;
-    let quoted_ident = field_quoter.quote_validator_param();
+    let quoted_ident = field_quoter.quote_validator_number();
 
     if let Validator::Range { ref min, ref max } = validation.validator {
         let min_err_param_quoted = if let Some(v) = min {
@@ -221,7 +331,8 @@ pub fn quote_range_validation(
             max.clone().map(|x| value_or_path_to_tokens(&x)).map(|x| quote!(#x as f64));
         let max_tokens = option_to_tokens(&max_tokens);
 
-        let quoted_error = quote_error(&validation);
+        let quoted_error = quote_error(validation);
+        let value_param = quote_value_param(validation, quote!(#quoted_ident));
         let quoted = quote!(
             if !::validator::validate_range(
                 #quoted_ident as f64,
@@ -231,7 +342,7 @@ pub fn quote_range_validation(
                 #quoted_error
                 #min_err_param_quoted
                 #max_err_param_quoted
-                err.add_param(::std::borrow::Cow::from("value"), &#quoted_ident);
+                #value_param
                 errors.add(#field_name, err);
             }
         );
@@ -248,13 +359,14 @@ pub fn quote_credit_card_validation(
     validation: &FieldValidation,
 ) -> proc_macro2::TokenStream {
     let field_name = &field_quoter.name;
-    let validator_param = field_quoter.quote_validator_param();
+    let validator_param = field_quoter.quote_validator_str();
 
-    let quoted_error = quote_error(&validation);
+    let quoted_error = quote_error(validation);
+    let value_param = quote_value_param(validation, quote!(#validator_param));
     let quoted = quote!(
         if !::validator::validate_credit_card(#validator_param) {
             #quoted_error
-            err.add_param(::std::borrow::Cow::from("value"), &#validator_param);
+            #value_param
             errors.add(#field_name, err);
         }
     );
@@ -268,13 +380,14 @@ pub fn quote_phone_validation(
     validation: &FieldValidation,
 ) -> proc_macro2::TokenStream {
     let field_name = &field_quoter.name;
-    let validator_param = field_quoter.quote_validator_param();
+    let validator_param = field_quoter.quote_validator_str();
 
-    let quoted_error = quote_error(&validation);
+    let quoted_error = quote_error(validation);
+    let value_param = quote_value_param(validation, quote!(#validator_param));
     let quoted = quote!(
         if !::validator::validate_phone(#validator_param) {
             #quoted_error
-            err.add_param(::std::borrow::Cow::from("value"), &#validator_param);
+            #value_param
             errors.add(#field_name, err);
         }
     );
@@ -288,13 +401,54 @@ pub fn quote_non_control_character_validation(
     validation: &FieldValidation,
 ) -> proc_macro2::TokenStream {
     let field_name = &field_quoter.name;
-    let validator_param = field_quoter.quote_validator_param();
+    let validator_param = field_quoter.quote_validator_str();
 
-    let quoted_error = quote_error(&validation);
+    let quoted_error = quote_error(validation);
+    let value_param = quote_value_param(validation, quote!(#validator_param));
     let quoted = quote!(
         if !::validator::validate_non_control_character(#validator_param) {
             #quoted_error
-            err.add_param(::std::borrow::Cow::from("value"), &#validator_param);
+            #value_param
+            errors.add(#field_name, err);
+        }
+    );
+
+    field_quoter.wrap_if_option(quoted)
+}
+
+pub fn quote_not_blank_validation(
+    field_quoter: &FieldQuoter,
+    validation: &FieldValidation,
+) -> proc_macro2::TokenStream {
+    let field_name = &field_quoter.name;
+    let validator_param = field_quoter.quote_validator_str();
+
+    let quoted_error = quote_error(validation);
+    let value_param = quote_value_param(validation, quote!(#validator_param));
+    let quoted = quote!(
+        if !::validator::validate_not_blank(#validator_param) {
+            #quoted_error
+            #value_param
+            errors.add(#field_name, err);
+        }
+    );
+
+    field_quoter.wrap_if_option(quoted)
+}
+
+pub fn quote_not_empty_validation(
+    field_quoter: &FieldQuoter,
+    validation: &FieldValidation,
+) -> proc_macro2::TokenStream {
+    let field_name = &field_quoter.name;
+    let validator_param = field_quoter.quote_validator_ref();
+
+    let quoted_error = quote_error(validation);
+    let value_param = quote_value_param(validation, quote!(#validator_param));
+    let quoted = quote!(
+        if !::validator::validate_not_empty(#validator_param) {
+            #quoted_error
+            #value_param
             errors.add(#field_name, err);
         }
     );
@@ -307,13 +461,14 @@ pub fn quote_url_validation(
     validation: &FieldValidation,
 ) -> proc_macro2::TokenStream {
     let field_name = &field_quoter.name;
-    let validator_param = field_quoter.quote_validator_param();
+    let validator_param = field_quoter.quote_validator_str();
 
-    let quoted_error = quote_error(&validation);
+    let quoted_error = quote_error(validation);
+    let value_param = quote_value_param(validation, quote!(#validator_param));
     let quoted = quote!(
         if !::validator::validate_url(#validator_param) {
             #quoted_error
-            err.add_param(::std::borrow::Cow::from("value"), &#validator_param);
+            #value_param
             errors.add(#field_name, err);
         }
     );
@@ -326,13 +481,14 @@ pub fn quote_email_validation(
     validation: &FieldValidation,
 ) -> proc_macro2::TokenStream {
     let field_name = &field_quoter.name;
-    let validator_param = field_quoter.quote_validator_param();
+    let validator_param = field_quoter.quote_validator_str();
 
-    let quoted_error = quote_error(&validation);
+    let quoted_error = quote_error(validation);
+    let value_param = quote_value_param(validation, quote!(#validator_param));
     let quoted = quote!(
         if !::validator::validate_email(#validator_param) {
             #quoted_error
-            err.add_param(::std::borrow::Cow::from("value"), &#validator_param);
+            #value_param
             errors.add(#field_name, err);
         }
     );
@@ -349,12 +505,23 @@ pub fn quote_must_match_validation(
 
     if let Validator::MustMatch(ref other) = validation.validator {
         let other_ident = syn::Ident::new(other, Span::call_site());
-        let quoted_error = quote_error(&validation);
+        let quoted_error = quote_error(validation);
+        // The compiler checks both fields have the same type, point to the field if they don't
+        let must_match = quote_spanned!(ident.span()=>
+            ::validator::validate_must_match(&self.#ident, &self.#other_ident)
+        );
+        let value_param = quote_value_param(validation, quote!(self.#ident));
+        // The other field is as sensitive as this one, it should have the same value
+        let other_param = if validation.sensitive {
+            quote!()
+        } else {
+            quote!(err.add_param(::std::borrow::Cow::from("other"), &self.#other_ident);)
+        };
         let quoted = quote!(
-            if !::validator::validate_must_match(&self.#ident, &self.#other_ident) {
+            if !#must_match {
                 #quoted_error
-                err.add_param(::std::borrow::Cow::from("value"), &self.#ident);
-                err.add_param(::std::borrow::Cow::from("other"), &self.#other_ident);
+                #value_param
+                #other_param
                 errors.add(#field_name, err);
             }
         );
@@ -365,6 +532,72 @@ pub fn quote_must_match_validation(
     unreachable!();
 }
 
+/// The pattern binding a reference to the value of the other field of a validation when it is
+/// wrapped in `other_options` `Option`s, and that reference
+fn quote_other_field(
+    other_ident: &syn::Ident,
+    other_options: usize,
+) -> (Option<proc_macro2::TokenStream>, proc_macro2::TokenStream) {
+    if other_options == 0 {
+        return (None, quote!(&self.#other_ident));
+    }
+
+    let binding = syn::Ident::new("__validator_other", Span::call_site());
+    let mut pattern = quote!(ref #binding);
+    for _ in 0..other_options {
+        pattern = quote!(Some(#pattern));
+    }
+    (Some(pattern), quote!(#binding))
+}
+
+pub fn quote_comparison_validation(
+    field_quoter: &FieldQuoter,
+    validation: &FieldValidation,
+) -> proc_macro2::TokenStream {
+    let ident = &field_quoter.ident;
+    let field_name = &field_quoter.name;
+
+    let (function, other) = match validation.validator {
+        Validator::GreaterThan(ref other) => ("validate_greater_than", other),
+        Validator::GreaterOrEqual(ref other) => ("validate_greater_or_equal", other),
+        Validator::LessThan(ref other) => ("validate_less_than", other),
+        Validator::LessOrEqual(ref other) => ("validate_less_or_equal", other),
+        Validator::NotEqual(ref other) => ("validate_not_equal", other),
+        _ => unreachable!(),
+    };
+    let function = syn::Ident::new(function, ident.span());
+    let other_ident = syn::Ident::new(other, Span::call_site());
+    let validator_param = field_quoter.quote_validator_ref();
+
+    // An `Option` other field is only compared when it has a value, like this one
+    let (other_pattern, other_param) = quote_other_field(&other_ident, validation.other_options);
+
+    let quoted_error = quote_error(validation);
+    // The compiler checks both fields can be compared, point to the field if they can't
+    let comparison = quote_spanned!(ident.span()=>
+        ::validator::#function(#validator_param, #other_param)
+    );
+    let value_param = quote_value_param(validation, validator_param);
+    let mut quoted = quote!(
+        if !#comparison {
+            #quoted_error
+            #value_param
+            err.add_param(::std::borrow::Cow::from("field"), &#field_name);
+            err.add_param(::std::borrow::Cow::from("other"), &#other);
+            errors.add(#field_name, err);
+        }
+    );
+    if let Some(pattern) = other_pattern {
+        quoted = quote!(
+            if let #pattern = self.#other_ident {
+                #quoted
+            }
+        );
+    }
+
+    field_quoter.wrap_if_option(quoted)
+}
+
 pub fn quote_custom_validation(
     field_quoter: &FieldQuoter,
     validation: &FieldValidation,
@@ -380,15 +613,26 @@ pub fn quote_custom_validation(
             quote!()
         };
 
+        let self_arg = if validation.custom_with_self { quote!(, self) } else { quote!() };
+        let args = &validation.custom_args;
+        let add_args_quoted = if args.is_empty() {
+            quote!()
+        } else {
+            quote!(err.add_param(::std::borrow::Cow::from("args"), &(#(#args,)*));)
+        };
+
+        let value_param = quote_value_param(validation, quote!(#validator_param));
         let quoted = quote!(
-            match #fn_ident(#validator_param) {
-                ::std::result::Result::Ok(()) => (),
-                ::std::result::Result::Err(mut err) => {
+            if let ::std::result::Result::Err(mut custom_errors) =
+                ::validator::ValidationResult::into_errors(#fn_ident(#validator_param #self_arg #(, #args)*), #field_name)
+            {
+                for err in custom_errors.field_errors_mut(#field_name).into_iter().flatten() {
                     #add_message_quoted
-                    err.add_param(::std::borrow::Cow::from("value"), &#validator_param);
-                    errors.add(#field_name, err);
-                },
-            };
+                    #add_args_quoted
+                    #value_param
+                }
+                errors.merge_with(custom_errors);
+            }
         );
 
         return field_quoter.wrap_if_option(quoted);
@@ -402,14 +646,15 @@ pub fn quote_contains_validation(
     validation: &FieldValidation,
 ) -> proc_macro2::TokenStream {
     let field_name = &field_quoter.name;
-    let validator_param = field_quoter.quote_validator_param();
+    let validator_param = field_quoter.quote_validator_ref();
 
     if let Validator::Contains(ref needle) = validation.validator {
-        let quoted_error = quote_error(&validation);
+        let quoted_error = quote_error(validation);
+        let value_param = quote_value_param(validation, quote!(#validator_param));
         let quoted = quote!(
             if !::validator::validate_contains(#validator_param, &#needle) {
                 #quoted_error
-                err.add_param(::std::borrow::Cow::from("value"), &#validator_param);
+                #value_param
                 err.add_param(::std::borrow::Cow::from("needle"), &#needle);
                 errors.add(#field_name, err);
             }
@@ -426,15 +671,16 @@ pub fn quote_regex_validation(
     validation: &FieldValidation,
 ) -> proc_macro2::TokenStream {
     let field_name = &field_quoter.name;
-    let validator_param = field_quoter.quote_validator_param();
+    let validator_param = field_quoter.quote_validator_str();
 
     if let Validator::Regex(ref re) = validation.validator {
         let re_ident: syn::Path = syn::parse_str(re).unwrap();
-        let quoted_error = quote_error(&validation);
+        let quoted_error = quote_error(validation);
+        let value_param = quote_value_param(validation, quote!(#validator_param));
         let quoted = quote!(
             if !#re_ident.is_match(#validator_param) {
                 #quoted_error
-                err.add_param(::std::borrow::Cow::from("value"), &#validator_param);
+                #value_param
                 errors.add(#field_name, err);
             }
         );
@@ -448,10 +694,67 @@ pub fn quote_regex_validation(
 pub fn quote_nested_validation(field_quoter: &FieldQuoter) -> proc_macro2::TokenStream {
     let field_name = &field_quoter.name;
     let validator_field = field_quoter.quote_validator_field();
-    let quoted = quote!(result = ::validator::ValidationErrors::merge(result, #field_name, #validator_field.validate()););
+    let method = if field_quoter.fail_fast { quote!(validate_fast) } else { quote!(validate) };
+    let quoted = quote!(result = ::validator::ValidationErrors::merge(result, #field_name, #validator_field.#method()););
     field_quoter.wrap_if_option(field_quoter.wrap_if_vector(quoted))
 }
 
+/// Runs the validations given to `each` on every element of the field, the errors
+/// being keyed by the index of the element like for nested vectors, or by the key
+/// for the values of a map
+pub fn quote_each_validation(
+    field_quoter: &FieldQuoter,
+    element_quoter: &FieldQuoter,
+    element_validations: &[proc_macro2::TokenStream],
+    is_map: bool,
+) -> proc_macro2::TokenStream {
+    let field_name = &field_quoter.name;
+    let element_ident = &element_quoter.ident;
+    let collection =
+        if field_quoter.is_bound() { quote!(#element_ident) } else { quote!(self.#element_ident) };
+
+    let element_validations = if field_quoter.fail_fast {
+        quote!((|| {
+            let mut errors = ::validator::ValidationErrors::new();
+            #(
+                #element_validations
+                if !errors.is_empty() {
+                    return ::std::result::Result::Err(errors);
+                }
+            )*
+            ::std::result::Result::Ok(())
+        })())
+    } else {
+        quote!({
+            let mut errors = ::validator::ValidationErrors::new();
+            #(#element_validations)*
+            if errors.is_empty() {
+                ::std::result::Result::Ok(())
+            } else {
+                ::std::result::Result::Err(errors)
+            }
+        })
+    };
+    let results = element_quoter.quote_results(
+        quote!(#collection.iter()),
+        quote!({
+            let element_result = #element_validations;
+            ::validator::ValidationErrors::merge(::std::result::Result::Ok(()), #field_name, element_result)
+        }),
+        is_map,
+    );
+    let merge = if is_map { quote!(merge_map) } else { quote!(merge_all) };
+
+    let quoted = quote!(
+        if !::validator::ValidationErrors::has_error(&result, #field_name) {
+            #results
+            result = ::validator::ValidationErrors::#merge(result, #field_name, results);
+        }
+    );
+
+    field_quoter.wrap_if_option(quoted)
+}
+
 pub fn quote_field_validation(
     field_quoter: &FieldQuoter,
     validation: &FieldValidation,
@@ -460,37 +763,58 @@ pub fn quote_field_validation(
 ) {
     match validation.validator {
         Validator::Length { .. } => {
-            validations.push(quote_length_validation(&field_quoter, validation))
+            validations.push(quote_length_validation(field_quoter, validation))
         }
         Validator::Range { .. } => {
-            validations.push(quote_range_validation(&field_quoter, validation))
+            validations.push(quote_range_validation(field_quoter, validation))
+        }
+        Validator::Email => validations.push(quote_email_validation(field_quoter, validation)),
+        Validator::Url => validations.push(quote_url_validation(field_quoter, validation)),
+        Validator::NotBlank => {
+            validations.push(quote_not_blank_validation(field_quoter, validation))
+        }
+        Validator::NotEmpty => {
+            validations.push(quote_not_empty_validation(field_quoter, validation))
         }
-        Validator::Email => validations.push(quote_email_validation(&field_quoter, validation)),
-        Validator::Url => validations.push(quote_url_validation(&field_quoter, validation)),
         Validator::MustMatch(_) => {
-            validations.push(quote_must_match_validation(&field_quoter, validation))
+            validations.push(quote_must_match_validation(field_quoter, validation))
         }
-        Validator::Custom(_) => {
-            validations.push(quote_custom_validation(&field_quoter, validation))
+        Validator::GreaterThan(_)
+        | Validator::GreaterOrEqual(_)
+        | Validator::LessThan(_)
+        | Validator::LessOrEqual(_)
+        | Validator::NotEqual(_) => {
+            validations.push(quote_comparison_validation(field_quoter, validation))
         }
+        Validator::Custom(_) => validations.push(quote_custom_validation(field_quoter, validation)),
         Validator::Contains(_) => {
-            validations.push(quote_contains_validation(&field_quoter, validation))
+            validations.push(quote_contains_validation(field_quoter, validation))
         }
-        Validator::Regex(_) => validations.push(quote_regex_validation(&field_quoter, validation)),
+        Validator::Regex(_) => validations.push(quote_regex_validation(field_quoter, validation)),
         #[cfg(feature = "card")]
         Validator::CreditCard => {
-            validations.push(quote_credit_card_validation(&field_quoter, validation))
+            validations.push(quote_credit_card_validation(field_quoter, validation))
         }
         #[cfg(feature = "phone")]
-        Validator::Phone => validations.push(quote_phone_validation(&field_quoter, validation)),
-        Validator::Nested => nested_validations.push(quote_nested_validation(&field_quoter)),
+        Validator::Phone => validations.push(quote_phone_validation(field_quoter, validation)),
+        Validator::Nested => nested_validations.push(quote_nested_validation(field_quoter)),
         #[cfg(feature = "unic")]
         Validator::NonControlCharacter => {
-            validations.push(quote_non_control_character_validation(&field_quoter, validation))
+            validations.push(quote_non_control_character_validation(field_quoter, validation))
         }
         Validator::Required | Validator::RequiredNested => {
-            validations.push(quote_required_validation(&field_quoter, validation))
+            validations.push(quote_required_validation(field_quoter, validation))
+        }
+        Validator::RequiredIf { .. }
+        | Validator::RequiredUnless { .. }
+        | Validator::RequiredWith(_)
+        | Validator::RequiredWithout(_) => {
+            validations.push(quote_conditional_required_validation(field_quoter, validation))
         }
+        // Only given on the struct, see `quote_presence_validation`
+        Validator::AtLeastOneOf(_)
+        | Validator::ExactlyOneOf(_)
+        | Validator::MutuallyExclusive(_) => unreachable!(),
     }
 }
 
@@ -503,16 +827,33 @@ pub fn quote_schema_validation(v: &SchemaValidation) -> proc_macro2::TokenStream
         quote!()
     };
 
-    let mut_err_token = if v.message.is_some() { quote!(mut) } else { quote!() };
+    let add_code_quoted = if let Some(ref c) = v.code {
+        quote!(err.code = ::std::borrow::Cow::from(#c);)
+    } else {
+        quote!()
+    };
+
+    let (mut_token, edit_errors) = if v.message.is_some() || v.code.is_some() {
+        (
+            quote!(mut),
+            quote!(
+                for err in custom_errors.field_errors_mut("__all__").into_iter().flatten() {
+                    #add_code_quoted
+                    #add_message_quoted
+                }
+            ),
+        )
+    } else {
+        (quote!(), quote!())
+    };
 
     let quoted = quote!(
-        match #fn_ident(self) {
-            ::std::result::Result::Ok(()) => (),
-            ::std::result::Result::Err(#mut_err_token err) => {
-                #add_message_quoted
-                errors.add("__all__", err);
-            },
-        };
+        if let ::std::result::Result::Err(#mut_token custom_errors) =
+            ::validator::ValidationResult::into_errors(#fn_ident(self), "__all__")
+        {
+            #edit_errors
+            errors.merge_with(custom_errors);
+        }
     );
 
     if !v.skip_on_field_errors {
@@ -524,23 +865,108 @@ pub fn quote_schema_validation(v: &SchemaValidation) -> proc_macro2::TokenStream
     )
 }
 
+pub fn quote_presence_validation(v: &PresenceValidation) -> proc_macro2::TokenStream {
+    let idents = v.fields.iter().map(|(ident, _)| ident);
+    let names = v.fields.iter().map(|(_, name)| name).collect::<Vec<_>>();
+    let code = &v.code;
+    let add_message_quoted = if let Some(ref m) = v.message {
+        quote!(err.message = Some(::std::borrow::Cow::from(#m));)
+    } else {
+        quote!()
+    };
+
+    let invalid = match v.rule {
+        PresenceRule::AtLeastOneOf => quote!(count == 0),
+        PresenceRule::ExactlyOneOf => quote!(count != 1),
+        PresenceRule::MutuallyExclusive => quote!(count > 1),
+    };
+    // When no field has a value, all of them are missing one, otherwise the ones with a value are extra
+    let add_error = if v.on_fields {
+        quote!(for field in present.iter().filter(|field| count == 0 || field.1) {
+            errors.add(field.0, err.clone());
+        })
+    } else {
+        quote!(errors.add("__all__", err);)
+    };
+
+    quote!({
+        let present = [#((#names, self.#idents.is_some())),*];
+        let count = present.iter().filter(|field| field.1).count();
+        if #invalid {
+            let mut err = ::validator::ValidationError::new(#code);
+            #add_message_quoted
+            err.add_param(::std::borrow::Cow::from("fields"), &[#(#names),*]);
+            #add_error
+        }
+    })
+}
+
 pub fn quote_schema_validations(validation: &[SchemaValidation]) -> Vec<proc_macro2::TokenStream> {
     validation.iter().map(quote_schema_validation).collect()
 }
 
+/// `required_if`, `required_unless`, `required_with` and `required_without`
+pub fn quote_conditional_required_validation(
+    field_quoter: &FieldQuoter,
+    validation: &FieldValidation,
+) -> proc_macro2::TokenStream {
+    let field_name = &field_quoter.name;
+    let validator_param = field_quoter.quote_source_ref();
+
+    let (other, value, negate) = match validation.validator {
+        Validator::RequiredIf { ref other, ref value } => (other, Some(value), false),
+        Validator::RequiredUnless { ref other, ref value } => (other, Some(value), true),
+        Validator::RequiredWith(ref other) => (other, None, false),
+        Validator::RequiredWithout(ref other) => (other, None, true),
+        _ => unreachable!(),
+    };
+    let other_ident = syn::Ident::new(other, Span::call_site());
+    let (other_pattern, other_param) = quote_other_field(&other_ident, validation.other_options);
+
+    // Whether the other field has the value, or has any value for `required_with(out)`
+    let matches = match value {
+        Some(value) => {
+            let value: syn::Expr = syn::parse_str(value).unwrap();
+            quote_spanned!(other_ident.span()=> #other_param == &#value)
+        }
+        None => quote!(true),
+    };
+    let matches = match other_pattern {
+        Some(pattern) => quote!(
+            match self.#other_ident {
+                #pattern => #matches,
+                _ => false,
+            }
+        ),
+        None => matches,
+    };
+    let condition = if negate { quote!(!(#matches)) } else { matches };
+
+    let quoted_error = quote_error(validation);
+    let value_param = quote_value_param(validation, quote!(#validator_param));
+    quote!(
+        if #condition && !::validator::validate_required(#validator_param) {
+            #quoted_error
+            #value_param
+            err.add_param(::std::borrow::Cow::from("other"), &#other);
+            errors.add(#field_name, err);
+        }
+    )
+}
+
 pub fn quote_required_validation(
     field_quoter: &FieldQuoter,
     validation: &FieldValidation,
 ) -> proc_macro2::TokenStream {
     let field_name = &field_quoter.name;
-    let ident = &field_quoter.ident;
-    let validator_param = quote!(&self.#ident);
+    let validator_param = field_quoter.quote_source_ref();
 
-    let quoted_error = quote_error(&validation);
+    let quoted_error = quote_error(validation);
+    let value_param = quote_value_param(validation, quote!(#validator_param));
     let quoted = quote!(
         if !::validator::validate_required(#validator_param) {
             #quoted_error
-            err.add_param(::std::borrow::Cow::from("value"), &#validator_param);
+            #value_param
             errors.add(#field_name, err);
         }
     );
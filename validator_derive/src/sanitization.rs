@@ -0,0 +1,205 @@
+//! `#[derive(Sanitize)]`: normalizes the fields in place with the sanitizers given
+//! in `#[sanitizer(...)]`, recursing into the nested fields like the nested validation.
+
+use proc_macro_error::abort;
+use quote::quote;
+use syn::spanned::Spanned;
+
+use crate::find_struct_bound;
+use crate::lit::lit_to_string;
+use crate::types::{find_nested_type, is_string, is_vec, strip_options, uses_type_params};
+
+#[derive(Debug, PartialEq)]
+enum Sanitizer {
+    Trim,
+    Lowercase,
+    Uppercase,
+    CollapseWhitespace,
+    #[cfg(feature = "unic")]
+    Nfc,
+    Custom(String),
+    Nested,
+}
+
+pub fn impl_sanitize(ast: &syn::DeriveInput) -> proc_macro2::TokenStream {
+    let fields = match ast.data {
+        syn::Data::Struct(syn::DataStruct { ref fields, .. }) => {
+            if fields.iter().any(|field| field.ident.is_none()) {
+                abort!(
+                    fields.span(),
+                    "struct has unnamed fields";
+                    help = "#[derive(Sanitize)] can only be used on structs with named fields";
+                );
+            }
+            fields.iter().cloned().collect::<Vec<_>>()
+        }
+        _ => abort!(ast.span(), "#[derive(Sanitize)] can only be used with structs"),
+    };
+
+    let type_params = ast.generics.type_params().map(|param| &param.ident).collect::<Vec<_>>();
+    let mut inferred_bounds: Vec<syn::WherePredicate> = vec![];
+    let mut sanitizations = vec![];
+
+    for field in &fields {
+        let sanitizers = find_sanitizers_for_field(field);
+        if sanitizers.is_empty() {
+            continue;
+        }
+
+        if sanitizers.contains(&Sanitizer::Nested) {
+            let nested_type = find_nested_type(&field.ty);
+            if uses_type_params(nested_type, &type_params) {
+                inferred_bounds.push(syn::parse_quote!(#nested_type: ::validator::Sanitize));
+            }
+        }
+
+        sanitizations.push(quote_field_sanitization(field, &sanitizers));
+    }
+
+    for attr in ast.attrs.iter().filter(|attr| attr.path.is_ident("sanitizer")) {
+        if crate::find_bound(attr).is_none() {
+            abort!(attr.span(), "Only `bound` is allowed as a struct level sanitizer attribute");
+        }
+    }
+
+    let mut generics = ast.generics.clone();
+    let bounds = find_struct_bound(&ast.attrs, "sanitizer").unwrap_or(inferred_bounds);
+    if !bounds.is_empty() {
+        generics.make_where_clause().predicates.extend(bounds);
+    }
+
+    let ident = &ast.ident;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    quote!(
+        impl #impl_generics ::validator::Sanitize for #ident #ty_generics #where_clause {
+            fn sanitize(&mut self) {
+                #(#sanitizations)*
+            }
+        }
+    )
+}
+
+fn find_sanitizers_for_field(field: &syn::Field) -> Vec<Sanitizer> {
+    let error = |span: proc_macro2::Span, msg: &str| -> ! { sanitizer_error(field, span, msg) };
+
+    let mut sanitizers = vec![];
+
+    for attr in field.attrs.iter().filter(|attr| attr.path.is_ident("sanitizer")) {
+        match attr.parse_meta() {
+            Ok(syn::Meta::List(syn::MetaList { ref nested, .. })) => {
+                if nested.is_empty() {
+                    error(attr.span(), "it needs at least one sanitizer");
+                }
+                for meta_item in nested {
+                    sanitizers.push(find_sanitizer(field, meta_item));
+                }
+            }
+            Ok(syn::Meta::Path(_)) => sanitizers.push(Sanitizer::Nested),
+            Ok(syn::Meta::NameValue(_)) => error(attr.span(), "unexpected name=value argument"),
+            Err(e) => error(attr.span(), &e.to_string()),
+        }
+    }
+
+    sanitizers
+}
+
+fn sanitizer_error(field: &syn::Field, span: proc_macro2::Span, msg: &str) -> ! {
+    abort!(
+        span,
+        "Invalid attribute #[sanitizer] on field `{}`: {}",
+        field.ident.as_ref().unwrap(),
+        msg
+    );
+}
+
+fn find_sanitizer(field: &syn::Field, meta_item: &syn::NestedMeta) -> Sanitizer {
+    let error = |span: proc_macro2::Span, msg: &str| -> ! { sanitizer_error(field, span, msg) };
+    let string_sanitizer = |name: &str, sanitizer: Sanitizer| {
+        let (inner, _) = strip_options(&field.ty);
+        if !is_string(inner) {
+            error(
+                field.ty.span(),
+                &format!("`{}` can only be used on a `String` or an `Option<String>`", name),
+            );
+        }
+        sanitizer
+    };
+
+    match *meta_item {
+        syn::NestedMeta::Meta(syn::Meta::Path(ref path)) => {
+            let name = path.get_ident().map(|i| i.to_string()).unwrap_or_default();
+            match name.as_ref() {
+                "trim" => string_sanitizer("trim", Sanitizer::Trim),
+                "lowercase" => string_sanitizer("lowercase", Sanitizer::Lowercase),
+                "uppercase" => string_sanitizer("uppercase", Sanitizer::Uppercase),
+                "collapse_whitespace" => {
+                    string_sanitizer("collapse_whitespace", Sanitizer::CollapseWhitespace)
+                }
+                #[cfg(feature = "unic")]
+                "nfc" => string_sanitizer("nfc", Sanitizer::Nfc),
+                "nested" => Sanitizer::Nested,
+                _ => error(path.span(), &format!("unexpected sanitizer `{}`", name)),
+            }
+        }
+        syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+            ref path,
+            ref lit,
+            ..
+        })) if path.is_ident("custom") => match lit_to_string(lit) {
+            Some(s) => Sanitizer::Custom(s),
+            None => error(lit.span(), "invalid argument for `custom`: only strings are allowed"),
+        },
+        _ => error(meta_item.span(), "unexpected sanitizer"),
+    }
+}
+
+fn quote_field_sanitization(
+    field: &syn::Field,
+    sanitizers: &[Sanitizer],
+) -> proc_macro2::TokenStream {
+    let ident = field.ident.as_ref().unwrap();
+    let (inner, depth) = strip_options(&field.ty);
+    // A `&mut` to the value, bound by the `if let` when the field is an `Option`
+    let target = if depth == 0 { quote!(&mut self.#ident) } else { quote!(#ident) };
+
+    let calls = sanitizers.iter().map(|sanitizer| match *sanitizer {
+        Sanitizer::Trim => quote!(::validator::sanitize_trim(#target);),
+        Sanitizer::Lowercase => quote!(::validator::sanitize_lowercase(#target);),
+        Sanitizer::Uppercase => quote!(::validator::sanitize_uppercase(#target);),
+        Sanitizer::CollapseWhitespace => {
+            quote!(::validator::sanitize_collapse_whitespace(#target);)
+        }
+        #[cfg(feature = "unic")]
+        Sanitizer::Nfc => quote!(::validator::sanitize_nfc(#target);),
+        Sanitizer::Custom(ref fun) => {
+            let fn_ident: syn::Path = syn::parse_str(fun).unwrap();
+            quote!(#fn_ident(#target);)
+        }
+        Sanitizer::Nested => {
+            if is_vec(inner) {
+                quote!(
+                    for #ident in (#target).iter_mut() {
+                        ::validator::Sanitize::sanitize(#ident);
+                    }
+                )
+            } else {
+                quote!(::validator::Sanitize::sanitize(#target);)
+            }
+        }
+    });
+    let quoted = quote!(#(#calls)*);
+
+    if depth == 0 {
+        return quoted;
+    }
+
+    let mut pattern = quote!(ref mut #ident);
+    for _ in 0..depth {
+        pattern = quote!(Some(#pattern));
+    }
+    quote!(
+        if let #pattern = self.#ident {
+            #quoted
+        }
+    )
+}
@@ -0,0 +1,214 @@
+//! Structural analysis of field types.
+//!
+//! We only look at the shape of a type when it changes the code we need to generate,
+//! eg unwrapping an `Option` or iterating on a `Vec`. Everything else is left to the
+//! traits the validators rely on (`AsRef<str>`, `HasLen`, `Contains`...) so type aliases,
+//! generics and fully qualified paths work the same as the plain types.
+
+/// The number types that are passed by value to the validators
+static NUMBER_TYPES: [&str; 12] =
+    ["usize", "u8", "u16", "u32", "u64", "isize", "i8", "i16", "i32", "i64", "f32", "f64"];
+
+/// The collections the validators know are not strings or numbers
+static COLLECTION_TYPES: [&str; 7] =
+    ["Vec", "VecDeque", "LinkedList", "HashSet", "BTreeSet", "HashMap", "BTreeMap"];
+
+/// Looks through the invisible groups `macro_rules!` can wrap types in
+fn ungroup(ty: &syn::Type) -> &syn::Type {
+    match *ty {
+        syn::Type::Group(syn::TypeGroup { ref elem, .. })
+        | syn::Type::Paren(syn::TypeParen { ref elem, .. }) => ungroup(elem),
+        _ => ty,
+    }
+}
+
+/// The last segment of the path of a type, if it is a path, eg `Vec<T>` for `std::vec::Vec<T>`
+fn last_segment(ty: &syn::Type) -> Option<&syn::PathSegment> {
+    match *ungroup(ty) {
+        syn::Type::Path(syn::TypePath { qself: None, ref path }) => path.segments.last(),
+        _ => None,
+    }
+}
+
+/// The generic type arguments of the last segment of a type path
+fn type_arguments(ty: &syn::Type) -> Vec<&syn::Type> {
+    match last_segment(ty).map(|s| &s.arguments) {
+        Some(syn::PathArguments::AngleBracketed(ref args)) => args
+            .args
+            .iter()
+            .filter_map(|arg| match arg {
+                syn::GenericArgument::Type(ref ty) => Some(ty),
+                _ => None,
+            })
+            .collect(),
+        _ => vec![],
+    }
+}
+
+/// Whether the type is a path ending in `name`, like `Option`, `std::option::Option`...
+fn is_named(ty: &syn::Type, name: &str) -> bool {
+    last_segment(ty).map(|s| s.ident == name).unwrap_or(false)
+}
+
+/// Returns the type inside of an `Option`
+pub fn option_inner(ty: &syn::Type) -> Option<&syn::Type> {
+    if !is_named(ty, "Option") {
+        return None;
+    }
+    let args = type_arguments(ty);
+    if args.len() == 1 {
+        Some(args[0])
+    } else {
+        None
+    }
+}
+
+/// Removes all the `Option` layers of a type, returning the inner type and the number of layers
+pub fn strip_options(ty: &syn::Type) -> (&syn::Type, usize) {
+    match option_inner(ty) {
+        Some(inner) => {
+            let (inner, depth) = strip_options(inner);
+            (inner, depth + 1)
+        }
+        None => (ungroup(ty), 0),
+    }
+}
+
+pub fn is_reference(ty: &syn::Type) -> bool {
+    matches!(*ungroup(ty), syn::Type::Reference(_))
+}
+
+/// Whether the type is a `Vec`, which nested validation iterates on
+pub fn is_vec(ty: &syn::Type) -> bool {
+    is_named(ty, "Vec")
+}
+
+pub fn is_cow(ty: &syn::Type) -> bool {
+    is_named(ty, "Cow")
+}
+
+/// Whether the type is a `String`, the only type the string sanitizers can modify in place
+pub fn is_string(ty: &syn::Type) -> bool {
+    is_named(ty, "String")
+}
+
+/// Whether the type is one of the primitive number types
+pub fn is_number(ty: &syn::Type) -> bool {
+    match last_segment(ty) {
+        Some(s) if s.arguments.is_empty() => NUMBER_TYPES.iter().any(|n| s.ident == n),
+        _ => false,
+    }
+}
+
+/// Whether the type is known not to be a number or a string: `bool`, `char`,
+/// a collection of the standard library, a slice, an array or a tuple
+fn is_other_known_type(ty: &syn::Type) -> bool {
+    match *ungroup(ty) {
+        syn::Type::Reference(syn::TypeReference { ref elem, .. }) => is_other_known_type(elem),
+        syn::Type::Slice(_) | syn::Type::Array(_) | syn::Type::Tuple(_) => true,
+        _ => {
+            is_named(ty, "bool")
+                || is_named(ty, "char")
+                || COLLECTION_TYPES.iter().any(|name| is_named(ty, name))
+        }
+    }
+}
+
+/// Whether the type is known not to be a string
+pub fn is_known_non_string(ty: &syn::Type) -> bool {
+    let ty = match *ungroup(ty) {
+        syn::Type::Reference(syn::TypeReference { ref elem, .. }) => elem,
+        _ => ty,
+    };
+    is_number(ty) || is_other_known_type(ty)
+}
+
+/// Whether the type is known not to have a length
+pub fn is_known_without_len(ty: &syn::Type) -> bool {
+    let ty = match *ungroup(ty) {
+        syn::Type::Reference(syn::TypeReference { ref elem, .. }) => elem,
+        _ => ty,
+    };
+    is_number(ty) || is_named(ty, "bool") || is_named(ty, "char")
+}
+
+/// Whether the type is known not to be a number
+pub fn is_known_non_number(ty: &syn::Type) -> bool {
+    is_reference(ty)
+        || is_cow(ty)
+        || is_named(ty, "String")
+        || is_named(ty, "str")
+        || is_other_known_type(ty)
+}
+
+/// Finds the type of the elements of a collection, looking through references and `Option`s.
+/// The boolean is `true` if the collection is a map, in which case its elements are the values.
+pub fn find_element_type(ty: &syn::Type) -> Option<(&syn::Type, bool)> {
+    match *ungroup(ty) {
+        syn::Type::Reference(syn::TypeReference { ref elem, .. }) => find_element_type(elem),
+        syn::Type::Slice(syn::TypeSlice { ref elem, .. })
+        | syn::Type::Array(syn::TypeArray { ref elem, .. }) => Some((elem, false)),
+        _ => {
+            let args = type_arguments(ty);
+            let name = last_segment(ty)?.ident.to_string();
+            match name.as_ref() {
+                "Option" => find_element_type(args.first()?),
+                "Vec" | "VecDeque" | "LinkedList" | "HashSet" | "BTreeSet" => {
+                    Some((args.first()?, false))
+                }
+                "HashMap" | "BTreeMap" => Some((args.get(1)?, true)),
+                _ => None,
+            }
+        }
+    }
+}
+
+/// The type nested validation calls `validate()` on: the field type without its `Option`s
+/// and, for a `Vec`, the type of its elements
+pub fn find_nested_type(ty: &syn::Type) -> &syn::Type {
+    let (inner, _) = strip_options(ty);
+    if is_vec(inner) {
+        if let Some(element) = type_arguments(inner).first() {
+            return element;
+        }
+    }
+    inner
+}
+
+/// Whether the type mentions one of the given generic type parameters
+pub fn uses_type_params(ty: &syn::Type, params: &[&syn::Ident]) -> bool {
+    fn uses_idents(tokens: proc_macro2::TokenStream, params: &[&syn::Ident]) -> bool {
+        tokens.into_iter().any(|token| match token {
+            proc_macro2::TokenTree::Ident(ref ident) => params.contains(&ident),
+            proc_macro2::TokenTree::Group(ref group) => uses_idents(group.stream(), params),
+            _ => false,
+        })
+    }
+
+    uses_idents(quote::quote!(#ty), params)
+}
+
+/// The JSON type of the values of a type, if it is known
+#[cfg(feature = "json_schema")]
+pub fn json_type(ty: &syn::Type) -> Option<&'static str> {
+    let ty = match *ungroup(ty) {
+        syn::Type::Reference(syn::TypeReference { ref elem, .. }) => elem,
+        _ => ty,
+    };
+    if is_named(ty, "f32") || is_named(ty, "f64") {
+        Some("number")
+    } else if is_number(ty) {
+        Some("integer")
+    } else if is_named(ty, "bool") {
+        Some("boolean")
+    } else if is_cow(ty) || is_named(ty, "String") || is_named(ty, "str") || is_named(ty, "char") {
+        Some("string")
+    } else {
+        None
+    }
+}
+
+/// Renders a type for error messages
+pub fn type_to_string(ty: &syn::Type) -> String {
+    quote::quote!(#ty).to_string().replace(' ', "")
+}
@@ -1,7 +1,10 @@
+use std::collections::HashMap;
+
 use proc_macro_error::abort;
 use validator_types::Validator;
 
 use crate::lit::*;
+use crate::types::{strip_options, type_to_string};
 use proc_macro2::Span;
 use syn::spanned::Spanned;
 
@@ -13,16 +16,79 @@ pub struct SchemaValidation {
     pub message: Option<String>,
 }
 
+/// The rules about which `Option` fields of a struct can have a value
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PresenceRule {
+    AtLeastOneOf,
+    ExactlyOneOf,
+    MutuallyExclusive,
+}
+
+impl PresenceRule {
+    pub fn from_name(name: &str) -> Option<PresenceRule> {
+        match name {
+            "at_least_one_of" => Some(PresenceRule::AtLeastOneOf),
+            "exactly_one_of" => Some(PresenceRule::ExactlyOneOf),
+            "mutually_exclusive" => Some(PresenceRule::MutuallyExclusive),
+            _ => None,
+        }
+    }
+
+    pub fn code(self) -> &'static str {
+        match self {
+            PresenceRule::AtLeastOneOf => "at_least_one_of",
+            PresenceRule::ExactlyOneOf => "exactly_one_of",
+            PresenceRule::MutuallyExclusive => "mutually_exclusive",
+        }
+    }
+}
+
+/// A struct level rule like `#[validate(exactly_one_of("email", "phone"))]`
+#[derive(Debug)]
+pub struct PresenceValidation {
+    pub rule: PresenceRule,
+    /// The idents of the fields with their names in the errors
+    pub fields: Vec<(syn::Ident, String)>,
+    /// Whether the errors go on the fields involved rather than on `__all__`
+    pub on_fields: bool,
+    pub code: String,
+    pub message: Option<String>,
+}
+
+/// The validations given in a `#[validate(...)]` on the struct
+#[derive(Debug)]
+pub enum StructValidation {
+    Schema(SchemaValidation),
+    Presence(PresenceValidation),
+}
+
 #[derive(Debug)]
 pub struct FieldValidation {
     pub code: String,
     pub message: Option<String>,
     pub validator: Validator,
+    /// Whether the field has `sensitive`, redacting the value in the errors
+    pub sensitive: bool,
+    /// The extra arguments given to a `custom` function after the field, from `args(...)`
+    pub custom_args: Vec<syn::Expr>,
+    /// Whether a `custom` function also gets the struct after the field, from `with_self`
+    pub custom_with_self: bool,
+    /// How many `Option`s wrap the other field of a comparison or of a conditional `required_*`,
+    /// which only looks at its value once it has one
+    pub other_options: usize,
 }
 
 impl FieldValidation {
     pub fn new(validator: Validator) -> FieldValidation {
-        FieldValidation { code: validator.code().to_string(), validator, message: None }
+        FieldValidation {
+            code: validator.code().to_string(),
+            validator,
+            message: None,
+            sensitive: false,
+            custom_args: vec![],
+            custom_with_self: false,
+            other_options: 0,
+        }
     }
 }
 
@@ -97,7 +163,7 @@ pub fn extract_length_validation(
     FieldValidation {
         message,
         code: code.unwrap_or_else(|| validator.code().to_string()),
-        validator,
+        ..FieldValidation::new(validator)
     }
 }
 
@@ -158,11 +224,11 @@ pub fn extract_range_validation(
     FieldValidation {
         message,
         code: code.unwrap_or_else(|| validator.code().to_string()),
-        validator,
+        ..FieldValidation::new(validator)
     }
 }
 
-/// Extract url/email/phone/non_control_character field validation with a code or a message
+/// Extract url/email/phone/non_control_character/not_blank/not_empty field validation with a code or a message
 pub fn extract_argless_validation(
     validator_name: String,
     field: String,
@@ -198,6 +264,8 @@ pub fn extract_argless_validation(
 
     let validator = match validator_name.as_ref() {
         "email" => Validator::Email,
+        "not_blank" => Validator::NotBlank,
+        "not_empty" => Validator::NotEmpty,
         #[cfg(feature = "card")]
         "credit_card" => Validator::CreditCard,
         #[cfg(feature = "phone")]
@@ -210,11 +278,11 @@ pub fn extract_argless_validation(
     FieldValidation {
         message,
         code: code.unwrap_or_else(|| validator.code().to_string()),
-        validator,
+        ..FieldValidation::new(validator)
     }
 }
 
-/// For custom, contains, regex, must_match
+/// For contains, regex, must_match, required_with, required_without and the comparisons to another field
 pub fn extract_one_arg_validation(
     val_name: &str,
     validator_name: String,
@@ -271,17 +339,294 @@ pub fn extract_one_arg_validation(
     }
 
     let validator = match validator_name.as_ref() {
-        "custom" => Validator::Custom(value.unwrap()),
         "contains" => Validator::Contains(value.unwrap()),
         "must_match" => Validator::MustMatch(value.unwrap()),
         "regex" => Validator::Regex(value.unwrap()),
+        "required_with" => Validator::RequiredWith(value.unwrap()),
+        "required_without" => Validator::RequiredWithout(value.unwrap()),
+        v => comparison_validator(v, value.unwrap()),
+    };
+
+    FieldValidation {
+        message,
+        code: code.unwrap_or_else(|| validator.code().to_string()),
+        ..FieldValidation::new(validator)
+    }
+}
+
+/// The names of the validators comparing a field to another one
+pub const COMPARISONS: &[&str] =
+    &["greater_than", "greater_or_equal", "less_than", "less_or_equal", "not_equal"];
+
+/// The comparison `validator_name` to the field `other`
+pub fn comparison_validator(validator_name: &str, other: String) -> Validator {
+    match validator_name {
+        "greater_than" => Validator::GreaterThan(other),
+        "greater_or_equal" => Validator::GreaterOrEqual(other),
+        "less_than" => Validator::LessThan(other),
+        "less_or_equal" => Validator::LessOrEqual(other),
+        "not_equal" => Validator::NotEqual(other),
         _ => unreachable!(),
+    }
+}
+
+/// For required_if and required_unless, eg `required_if(field = "kind", value = "business")`
+pub fn extract_required_if_validation(
+    validator_name: String,
+    field: String,
+    meta_items: &[syn::NestedMeta],
+) -> FieldValidation {
+    let mut other = None;
+    let mut value = None;
+    let (message, code) = extract_message_and_code(&validator_name, &field, meta_items);
+
+    for meta_item in meta_items {
+        match *meta_item {
+            syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                ref path,
+                ref lit,
+                ..
+            })) => {
+                let ident = path.get_ident().unwrap();
+                match ident.to_string().as_ref() {
+                    "message" | "code" => continue,
+                    "field" => {
+                        other = match lit_to_string(lit) {
+                            Some(s) => Some(s),
+                            None => abort!(
+                                lit.span(),
+                                "Invalid argument type for `field` for validator `{}` on field `{}`: only a string is allowed",
+                                validator_name, field
+                            ),
+                        };
+                    }
+                    "value" => value = Some(quote::ToTokens::to_token_stream(lit).to_string()),
+                    v => abort!(
+                        path.span(),
+                        "Unknown argument `{}` for validator `{}` on field `{}`",
+                        v,
+                        validator_name,
+                        field
+                    ),
+                }
+            }
+            _ => abort!(
+                meta_item.span(),
+                "unexpected item {:?} while parsing `{}` validator",
+                meta_item,
+                validator_name
+            ),
+        }
+    }
+
+    let (other, value) = match (other, value) {
+        (Some(other), Some(value)) => (other, value),
+        _ => abort!(
+            meta_items.first().map_or_else(Span::call_site, |item| item.span()),
+            "Validator `{}` on field `{}` requires a `field` and a `value`",
+            validator_name,
+            field
+        ),
+    };
+    let validator = if validator_name == "required_if" {
+        Validator::RequiredIf { other, value }
+    } else {
+        Validator::RequiredUnless { other, value }
+    };
+
+    FieldValidation {
+        message,
+        code: code.unwrap_or_else(|| validator.code().to_string()),
+        ..FieldValidation::new(validator)
+    }
+}
+
+/// For custom with its optional arguments, eg `custom(function = "max_words", args(10))`,
+/// and `with_self` to also give it the struct
+pub fn extract_custom_validation(field: String, meta_items: &[syn::NestedMeta]) -> FieldValidation {
+    let mut function = None;
+    let mut custom_args = vec![];
+    let mut custom_with_self = false;
+    let (message, code) = extract_message_and_code("custom", &field, meta_items);
+
+    for meta_item in meta_items {
+        match *meta_item {
+            syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                ref path,
+                ref lit,
+                ..
+            })) => {
+                let ident = path.get_ident().unwrap();
+                match ident.to_string().as_ref() {
+                    "message" | "code" => continue,
+                    "function" => {
+                        function = match lit_to_string(lit) {
+                            Some(s) => Some(s),
+                            None => abort!(
+                                lit.span(),
+                                "Invalid argument type for `function` for validator `custom` on field `{}`: only a string is allowed",
+                                field
+                            ),
+                        };
+                    }
+                    v => abort!(
+                        path.span(),
+                        "Unknown argument `{}` for validator `custom` on field `{}`",
+                        v,
+                        field
+                    ),
+                }
+            }
+            syn::NestedMeta::Meta(syn::Meta::Path(ref path)) if path.is_ident("with_self") => {
+                custom_with_self = true;
+            }
+            syn::NestedMeta::Meta(syn::Meta::List(ref list)) if list.path.is_ident("args") => {
+                for arg in &list.nested {
+                    custom_args.push(match *arg {
+                        syn::NestedMeta::Lit(ref lit) => {
+                            syn::Expr::Lit(syn::ExprLit { attrs: vec![], lit: lit.clone() })
+                        }
+                        syn::NestedMeta::Meta(syn::Meta::Path(ref path)) => {
+                            syn::Expr::Path(syn::ExprPath { attrs: vec![], qself: None, path: path.clone() })
+                        }
+                        _ => abort!(
+                            arg.span(),
+                            "Invalid argument in `args` for validator `custom` on field `{}`: only literals and paths to constants are allowed",
+                            field
+                        ),
+                    });
+                }
+            }
+            _ => abort!(
+                meta_item.span(),
+                "unexpected item {:?} while parsing `custom` validator",
+                meta_item
+            ),
+        }
+    }
+
+    let function = match function {
+        Some(function) => function,
+        None => abort!(
+            meta_items.first().map_or_else(Span::call_site, |item| item.span()),
+            "Missing argument `function` for validator `custom` on field `{}`",
+            field
+        ),
     };
+    let validator = Validator::Custom(function);
 
     FieldValidation {
         message,
         code: code.unwrap_or_else(|| validator.code().to_string()),
-        validator,
+        custom_args,
+        custom_with_self,
+        ..FieldValidation::new(validator)
+    }
+}
+
+/// For `at_least_one_of`, `exactly_one_of` and `mutually_exclusive`, checking the fields exist and are `Option`s
+pub fn extract_presence_validation(
+    rule: PresenceRule,
+    path: &syn::Path,
+    meta_items: &[syn::NestedMeta],
+    field_types: &HashMap<String, syn::Type>,
+    field_names: &HashMap<String, String>,
+) -> PresenceValidation {
+    let validator_name = rule.code();
+    let mut fields: Vec<(syn::Ident, String)> = vec![];
+    let mut on_fields = false;
+    let mut code = None;
+    let mut message = None;
+
+    for meta_item in meta_items {
+        match *meta_item {
+            syn::NestedMeta::Lit(syn::Lit::Str(ref lit)) => {
+                let field = lit.value();
+                let ty = match field_types.get(&field) {
+                    Some(ty) => ty,
+                    None => abort!(
+                        lit.span(),
+                        "Invalid argument for `{}` validator: the field `{}` doesn't exist in struct",
+                        validator_name,
+                        field
+                    ),
+                };
+                if strip_options(ty).1 == 0 {
+                    abort!(
+                        lit.span(),
+                        "Invalid argument for `{}` validator: the field `{}` must be an `Option` but found `{}`",
+                        validator_name,
+                        field,
+                        type_to_string(ty)
+                    );
+                }
+                if fields.iter().any(|(ident, _)| *ident == field) {
+                    abort!(
+                        lit.span(),
+                        "Invalid argument for `{}` validator: the field `{}` is given twice",
+                        validator_name,
+                        field
+                    );
+                }
+                fields.push((syn::Ident::new(&field, lit.span()), field_names[&field].clone()));
+            }
+            syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                ref path,
+                ref lit,
+                ..
+            })) => match path.get_ident().unwrap().to_string().as_ref() {
+                name @ "message" | name @ "code" => {
+                    let value = match lit_to_string(lit) {
+                        Some(s) => s,
+                        None => abort!(
+                            lit.span(),
+                            "Invalid argument type for `{}` for validator `{}`: only a string is allowed",
+                            name,
+                            validator_name
+                        ),
+                    };
+                    if name == "code" {
+                        code = Some(value);
+                    } else {
+                        message = Some(value);
+                    }
+                }
+                "on_fields" => {
+                    on_fields = match lit_to_bool(lit) {
+                        Some(b) => b,
+                        None => abort!(
+                            lit.span(),
+                            "Invalid argument type for `on_fields` for validator `{}`: only a bool is allowed",
+                            validator_name
+                        ),
+                    };
+                }
+                v => abort!(
+                    path.span(),
+                    "Unknown argument `{}` for validator `{}`",
+                    v,
+                    validator_name
+                ),
+            },
+            _ => abort!(
+                meta_item.span(),
+                "Invalid argument for `{}` validator: only the names of the fields as strings, \
+                 `on_fields`, `code` and `message` are allowed",
+                validator_name
+            ),
+        }
+    }
+
+    if fields.len() < 2 {
+        abort!(path.span(), "Validator `{}` requires at least 2 fields", validator_name);
+    }
+
+    PresenceValidation {
+        rule,
+        fields,
+        on_fields,
+        code: code.unwrap_or_else(|| validator_name.to_string()),
+        message,
     }
 }
 
@@ -0,0 +1,134 @@
+use validator::Validate;
+
+#[test]
+fn can_validate_valid_comparisons() {
+    #[derive(Debug, Validate)]
+    struct TestStruct {
+        min: u32,
+        #[validate(greater_than = "min", not_equal = "min")]
+        max: u32,
+        #[validate(greater_or_equal = "min", less_or_equal = "max")]
+        default: u32,
+        #[validate(less_than = "max")]
+        discount: u32,
+    }
+
+    let s = TestStruct { min: 1, max: 10, default: 10, discount: 9 };
+
+    assert!(s.validate().is_ok());
+}
+
+#[test]
+fn failed_comparison_has_both_field_names_in_params() {
+    #[derive(Debug, Validate)]
+    struct TestStruct {
+        start: String,
+        #[validate(greater_than = "start")]
+        end: String,
+    }
+
+    let s = TestStruct { start: "2021-05-01".to_string(), end: "2021-04-30".to_string() };
+
+    let res = s.validate();
+    assert!(res.is_err());
+    let err = res.unwrap_err();
+    let errs = err.field_errors();
+    assert_eq!(errs.len(), 1);
+    assert_eq!(errs["end"].len(), 1);
+    assert_eq!(errs["end"][0].code, "greater_than");
+    assert_eq!(errs["end"][0].params["field"], "end");
+    assert_eq!(errs["end"][0].params["other"], "start");
+    assert_eq!(errs["end"][0].params["value"], "2021-04-30");
+    // The value of the other field isn't given, it may be sensitive
+    assert!(!errs["end"][0].params.contains_key("other_value"));
+}
+
+#[test]
+fn equal_values_fail_strict_comparisons() {
+    #[derive(Debug, Validate)]
+    struct TestStruct {
+        a: f64,
+        #[validate(greater_than = "a", less_than = "a", not_equal = "a")]
+        b: f64,
+        #[validate(greater_or_equal = "a", less_or_equal = "a")]
+        c: f64,
+    }
+
+    let s = TestStruct { a: 1.5, b: 1.5, c: 1.5 };
+
+    let err = s.validate().unwrap_err();
+    let errs = err.field_errors();
+    let codes = errs["b"].iter().map(|e| e.code.as_ref()).collect::<Vec<_>>();
+    assert_eq!(codes, vec!["greater_than", "less_than", "not_equal"]);
+    assert!(!errs.contains_key("c"));
+}
+
+#[test]
+fn can_compare_fields_of_different_types() {
+    #[derive(Debug, Validate)]
+    struct TestStruct<'a> {
+        name: &'a str,
+        #[validate(not_equal = "name")]
+        password: String,
+    }
+
+    let s = TestStruct { name: "bob", password: "bob".to_string() };
+
+    let err = s.validate().unwrap_err();
+    assert_eq!(err.field_errors()["password"][0].code, "not_equal");
+    assert!(TestStruct { name: "bob", password: "hunter2".to_string() }.validate().is_ok());
+}
+
+#[test]
+fn optional_fields_are_only_compared_when_both_have_a_value() {
+    #[derive(Debug, Validate)]
+    struct TestStruct {
+        min: Option<i64>,
+        #[validate(greater_or_equal = "min")]
+        max: Option<i64>,
+        #[validate(less_than = "max")]
+        step: i64,
+    }
+
+    assert!(TestStruct { min: None, max: Some(1), step: 0 }.validate().is_ok());
+    assert!(TestStruct { min: Some(1), max: None, step: 10 }.validate().is_ok());
+
+    let err = TestStruct { min: Some(2), max: Some(1), step: 1 }.validate().unwrap_err();
+    let errs = err.field_errors();
+    assert_eq!(errs["max"][0].code, "greater_or_equal");
+    assert_eq!(errs["max"][0].params["value"], 1);
+    assert_eq!(errs["step"][0].code, "less_than");
+}
+
+#[test]
+fn can_specify_code_and_message_for_comparisons() {
+    #[derive(Debug, Validate)]
+    struct TestStruct {
+        price: u32,
+        #[validate(less_than(other = "price", code = "too_expensive", message = "oops"))]
+        sale_price: u32,
+    }
+
+    let s = TestStruct { price: 10, sale_price: 12 };
+    let err = s.validate().unwrap_err();
+    let errs = err.field_errors();
+    assert_eq!(errs["sale_price"][0].code, "too_expensive");
+    assert_eq!(errs["sale_price"][0].clone().message.unwrap(), "oops");
+    assert_eq!(errs["sale_price"][0].params["other"], "price");
+}
+
+#[test]
+fn comparison_uses_the_renamed_field_name() {
+    #[derive(Debug, Validate, serde::Deserialize)]
+    struct TestStruct {
+        min: u32,
+        #[serde(rename = "maxValue")]
+        #[validate(greater_than = "min")]
+        max: u32,
+    }
+
+    let err = TestStruct { min: 2, max: 1 }.validate().unwrap_err();
+    let errs = err.field_errors();
+    assert_eq!(errs["maxValue"][0].params["field"], "maxValue");
+    assert_eq!(errs["maxValue"][0].params["other"], "min");
+}
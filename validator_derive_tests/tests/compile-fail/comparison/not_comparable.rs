@@ -0,0 +1,10 @@
+use validator::Validate;
+
+#[derive(Validate)]
+struct Test {
+    start: String,
+    #[validate(less_or_equal(other = "start"))]
+    end: u32,
+}
+
+fn main() {}
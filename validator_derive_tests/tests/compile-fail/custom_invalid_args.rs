@@ -0,0 +1,9 @@
+use validator::Validate;
+
+#[derive(Validate)]
+struct Test {
+    #[validate(custom(function = "max_words", args(max = 2)))]
+    s: String,
+}
+
+fn main() {}
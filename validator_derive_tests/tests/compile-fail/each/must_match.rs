@@ -0,0 +1,10 @@
+use validator::Validate;
+
+#[derive(Validate)]
+struct Test {
+    #[validate(each(must_match = "other"))]
+    s: Vec<String>,
+    other: String,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use validator::Validate;
+
+#[derive(Validate)]
+struct Test {
+    #[validate(each(email))]
+    s: String,
+}
+
+fn main() {}
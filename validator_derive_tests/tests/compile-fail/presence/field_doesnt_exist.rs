@@ -0,0 +1,9 @@
+use validator::Validate;
+
+#[derive(Validate)]
+#[validate(exactly_one_of("email", "phone"))]
+struct Test {
+    email: Option<String>,
+}
+
+fn main() {}
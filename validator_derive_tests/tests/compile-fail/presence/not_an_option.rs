@@ -0,0 +1,10 @@
+use validator::Validate;
+
+#[derive(Validate)]
+#[validate(at_least_one_of("email", "phone"))]
+struct Test {
+    email: Option<String>,
+    phone: String,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use validator::Validate;
+
+#[derive(Validate)]
+struct Test {
+    kind: String,
+    #[validate(required_if(field = "kind", value = "business"))]
+    company_name: String,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use validator::Validate;
+
+#[derive(Validate)]
+struct Test {
+    #[validate(required_without = "phone")]
+    email: Option<String>,
+    phone: String,
+}
+
+fn main() {}
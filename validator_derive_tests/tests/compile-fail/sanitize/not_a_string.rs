@@ -0,0 +1,9 @@
+use validator::Sanitize;
+
+#[derive(Sanitize)]
+struct Test {
+    #[sanitizer(trim)]
+    s: Vec<String>,
+}
+
+fn main() {}
@@ -0,0 +1,182 @@
+#[macro_use]
+extern crate lazy_static;
+
+use std::borrow::Cow;
+
+use regex::Regex;
+use serde::Deserialize;
+use serde_json::json;
+use validator::{
+    Constraint, Constraints, FieldConstraints, Validate, ValidationError, Validator, ValueOrPath,
+};
+
+lazy_static! {
+    static ref RE_USERNAME: Regex = Regex::new(r"^[a-z0-9_]+$").unwrap();
+}
+
+const MAX_AGE: f64 = 99.0;
+
+fn valid_signup(_: &SignupData) -> Result<(), ValidationError> {
+    Ok(())
+}
+
+#[derive(Debug, Deserialize, Validate)]
+#[validate(schema(function = "valid_signup", code = "signup"))]
+struct SignupData {
+    #[serde(rename = "userName")]
+    #[validate(length(min = 3, max = 20), regex(path = "RE_USERNAME", message = "Invalid name"))]
+    user_name: String,
+    #[validate(range(min = 18, max = "MAX_AGE"))]
+    age: u32,
+    password: String,
+    #[validate(must_match = "password")]
+    password2: String,
+    #[validate(required, each(email(code = "mail")))]
+    emails: Option<Vec<String>>,
+}
+
+#[test]
+fn describes_the_validators_of_each_field() {
+    let constraints = SignupData::constraints();
+    let fields = constraints.iter().map(|c| c.field).collect::<Vec<_>>();
+    assert_eq!(fields, vec!["userName", "age", "password", "password2", "emails", "__all__"]);
+
+    let user_name = &constraints[0].constraints;
+    assert_eq!(user_name.len(), 2);
+    assert_eq!(
+        user_name[0].validator,
+        Validator::Length {
+            min: Some(ValueOrPath::Value(3)),
+            max: Some(ValueOrPath::Value(20)),
+            equal: None
+        }
+    );
+    assert_eq!(user_name[0].code, "length");
+    assert_eq!(user_name[0].params["min"], json!(3));
+    assert_eq!(user_name[0].params["max"], json!(20));
+    assert_eq!(user_name[1].validator, Validator::Regex("RE_USERNAME".to_string()));
+    assert_eq!(user_name[1].message, Some(Cow::from("Invalid name")));
+    assert_eq!(user_name[1].params["pattern"], json!("^[a-z0-9_]+$"));
+
+    let age = &constraints[1].constraints[0];
+    assert_eq!(
+        age.validator,
+        Validator::Range {
+            min: Some(ValueOrPath::Value(18.0)),
+            max: Some(ValueOrPath::Path("MAX_AGE".to_string())),
+        }
+    );
+    assert_eq!(age.params["min"], json!(18.0));
+    assert_eq!(age.params["max"], json!(99.0));
+
+    assert!(constraints[2].constraints.is_empty());
+    assert_eq!(constraints[3].constraints[0].params["other"], json!("password"));
+
+    let email = Constraint::new(Validator::Email, "mail", None, true);
+    assert_eq!(
+        constraints[4],
+        FieldConstraints {
+            field: "emails",
+            constraints: vec![Constraint::new(Validator::Required, "required", None, false), email],
+        }
+    );
+
+    assert_eq!(
+        constraints[5].constraints,
+        vec![Constraint::new(Validator::Custom("valid_signup".to_string()), "signup", None, false)]
+    );
+}
+
+#[test]
+fn records_the_args_of_custom_validators() {
+    fn max_words(_: &str, _: usize) -> Result<(), ValidationError> {
+        Ok(())
+    }
+
+    #[derive(Debug, Validate)]
+    struct Post {
+        #[validate(custom(function = "max_words", args(10)))]
+        title: String,
+    }
+
+    let constraints = Post::constraints();
+    let title = &constraints[0].constraints[0];
+    assert_eq!(title.validator, Validator::Custom("max_words".to_string()));
+    assert_eq!(title.params["args"], json!([10]));
+}
+
+#[test]
+fn records_the_other_field_of_conditional_validators() {
+    #[derive(Debug, Validate)]
+    struct Account {
+        kind: String,
+        #[validate(required_if(field = "kind", value = "business"))]
+        company_name: Option<String>,
+    }
+
+    let constraints = Account::constraints();
+    let company_name = &constraints[1].constraints[0];
+    assert_eq!(
+        company_name.validator,
+        Validator::RequiredIf { other: "kind".to_string(), value: "\"business\"".to_string() }
+    );
+    assert_eq!(company_name.params["other"], json!("kind"));
+    assert_eq!(company_name.params["value"], json!("business"));
+}
+
+#[test]
+fn merges_constraints_into_openapi_schema() {
+    let mut schema = json!({
+        "type": "object",
+        "properties": {
+            "userName": {"type": "string"},
+            "age": {"type": "integer", "format": "int32"},
+            "emails": {"type": ["array", "null"], "items": {"type": "string"}},
+        },
+    });
+    validator::openapi::merge_into_schema::<SignupData>(&mut schema);
+    assert_eq!(
+        schema,
+        json!({
+            "type": "object",
+            "properties": {
+                "userName": {
+                    "type": "string",
+                    "minLength": 3,
+                    "maxLength": 20,
+                    "pattern": "^[a-z0-9_]+$",
+                },
+                "age": {"type": "integer", "format": "int32", "minimum": 18, "maximum": 99},
+                "emails": {"type": ["array", "null"], "items": {"type": "string", "format": "email"}},
+            },
+            "required": ["emails"],
+        })
+    );
+}
+
+#[test]
+fn records_the_presence_rules_under_all() {
+    #[derive(Debug, Deserialize, Validate)]
+    #[validate(exactly_one_of("email", "phone", code = "contact"))]
+    struct Contact {
+        email: Option<String>,
+        #[serde(rename = "phoneNumber")]
+        phone: Option<String>,
+    }
+
+    let constraints = Contact::constraints();
+    assert_eq!(constraints[2].field, "__all__");
+    let rule = &constraints[2].constraints[0];
+    assert_eq!(
+        rule.validator,
+        Validator::ExactlyOneOf(vec!["email".to_string(), "phoneNumber".to_string()])
+    );
+    assert_eq!(rule.code, "contact");
+    assert_eq!(rule.params["fields"], json!(["email", "phoneNumber"]));
+
+    let has_value = |field: &str| json!({"required": [field], "properties": {field: {"not": {"type": "null"}}}});
+    assert_eq!(
+        validator::openapi::schema_fragment::<Contact>(),
+        json!({"properties": {}, "oneOf": [has_value("email"), has_value("phoneNumber")]})
+    );
+}
@@ -1,4 +1,4 @@
-use validator::{Validate, ValidationError};
+use validator::{Validate, ValidationError, ValidationErrors, ValidationErrorsKind};
 
 fn valid_custom_fn(_: &str) -> Result<(), ValidationError> {
     Ok(())
@@ -56,3 +56,230 @@ fn can_specify_message_for_custom_fn() {
     assert_eq!(errs["val"].len(), 1);
     assert_eq!(errs["val"][0].clone().message.unwrap(), "oops");
 }
+
+#[test]
+fn can_return_several_errors_from_custom_fn() {
+    fn validate_password(password: &str) -> Vec<ValidationError> {
+        let mut errors = vec![];
+        if password.len() < 8 {
+            errors.push(ValidationError::new("too_short"));
+        }
+        if !password.chars().any(|c| c.is_ascii_digit()) {
+            errors.push(ValidationError::new("no_digit"));
+        }
+        errors
+    }
+
+    fn validate_not_common(password: &str) -> Result<(), Vec<ValidationError>> {
+        if password == "password" {
+            return Err(vec![ValidationError::new("common")]);
+        }
+        Ok(())
+    }
+
+    #[derive(Debug, Validate)]
+    struct TestStruct {
+        #[validate(
+            length(max = 4),
+            custom(function = "validate_password", message = "weak"),
+            custom = "validate_not_common"
+        )]
+        val: String,
+    }
+
+    assert!(TestStruct { val: "pa55w0rd".to_string() }.validate().is_err());
+    let s = TestStruct { val: "password".to_string() };
+    let err = s.validate().unwrap_err();
+    let errs = err.field_errors();
+    let codes: Vec<_> = errs["val"].iter().map(|e| e.code.as_ref()).collect();
+    assert_eq!(codes, vec!["length", "no_digit", "common"]);
+    assert_eq!(errs["val"][1].clone().message.unwrap(), "weak");
+    assert_eq!(errs["val"][1].params["value"], "password");
+    assert_eq!(errs["val"][2].clone().message, None);
+}
+
+#[test]
+fn can_return_validation_errors_from_custom_fn() {
+    fn validate_range(range: &(u8, u8)) -> Result<(), ValidationErrors> {
+        if range.0 > range.1 {
+            let mut errors = ValidationErrors::new();
+            errors.add("0", ValidationError::new("inverted"));
+            errors.add("1", ValidationError::new("inverted"));
+            return Err(errors);
+        }
+        Ok(())
+    }
+
+    #[derive(Debug, Validate)]
+    struct TestStruct {
+        #[validate(custom(function = "validate_range", message = "oops"))]
+        range: (u8, u8),
+    }
+
+    assert!(TestStruct { range: (1, 2) }.validate().is_ok());
+    let err = TestStruct { range: (2, 1) }.validate().unwrap_err();
+    assert!(err.field_errors().is_empty());
+    match err.errors()["range"] {
+        ValidationErrorsKind::Struct(ref range) => {
+            let errs = range.field_errors();
+            assert_eq!(errs.len(), 2);
+            assert_eq!(errs["0"][0].code, "inverted");
+            assert_eq!(errs["1"][0].clone().message, None);
+        }
+        ref kind => panic!("Expected nested errors, got {:?}", kind),
+    }
+}
+
+#[test]
+fn validation_errors_from_custom_fn_do_not_clash_with_sibling_fields() {
+    fn validate_range(_: &(u8, u8)) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+        errors.add("name", ValidationError::new("inverted"));
+        Err(errors)
+    }
+
+    #[derive(Debug, Validate)]
+    struct TestStruct {
+        #[validate(length(min = 1))]
+        name: String,
+        #[validate(custom = "validate_range")]
+        range: (u8, u8),
+    }
+
+    let err = TestStruct { name: String::new(), range: (2, 1) }.validate().unwrap_err();
+    let errs = err.field_errors();
+    assert_eq!(errs["name"].len(), 1);
+    assert_eq!(errs["name"][0].code, "length");
+    match err.errors()["range"] {
+        ValidationErrorsKind::Struct(ref range) => {
+            assert_eq!(range.field_errors()["name"][0].code, "inverted")
+        }
+        ref kind => panic!("Expected nested errors, got {:?}", kind),
+    }
+}
+
+const MAX_TAGS: usize = 3;
+
+#[test]
+fn can_pass_args_to_custom_fn() {
+    fn max_words(val: &str, max: usize) -> Result<(), ValidationError> {
+        if val.split_whitespace().count() > max {
+            return Err(ValidationError::new("max_words"));
+        }
+        Ok(())
+    }
+
+    fn max_items(val: &[String], max: usize, what: &str) -> Result<(), ValidationError> {
+        if val.len() > max {
+            return Err(ValidationError::new("too_many").with_param("what", what));
+        }
+        Ok(())
+    }
+
+    #[derive(Debug, Validate)]
+    struct TestStruct {
+        #[validate(custom(function = "max_words", args(2)))]
+        title: String,
+        #[validate(custom(function = "max_words", args(1), message = "one word"))]
+        name: Option<String>,
+        #[validate(custom(function = "max_items", args(MAX_TAGS, "tags")))]
+        tags: Vec<String>,
+    }
+
+    let s = TestStruct {
+        title: "Hello world".to_string(),
+        name: Some("Bob".to_string()),
+        tags: vec!["a".to_string(); 3],
+    };
+    assert!(s.validate().is_ok());
+
+    let s = TestStruct {
+        title: "Hello new world".to_string(),
+        name: Some("Bob Smith".to_string()),
+        tags: vec!["a".to_string(); 4],
+    };
+    let err = s.validate().unwrap_err();
+    let errs = err.field_errors();
+    assert_eq!(errs["title"][0].code, "max_words");
+    assert_eq!(errs["title"][0].params["args"], serde_json::json!([2]));
+    assert_eq!(errs["name"][0].clone().message.unwrap(), "one word");
+    assert_eq!(errs["name"][0].params["args"], serde_json::json!([1]));
+    assert_eq!(errs["tags"][0].params["args"], serde_json::json!([3, "tags"]));
+    assert_eq!(errs["tags"][0].params["what"], "tags");
+}
+
+#[test]
+fn can_give_the_struct_to_custom_fn() {
+    fn after_start(end: u32, booking: &Booking) -> Result<(), ValidationError> {
+        if end <= booking.start {
+            return Err(ValidationError::new("before_start").with_param("start", &booking.start));
+        }
+        Ok(())
+    }
+
+    fn within_days(day: u32, booking: &Booking, max: u32) -> Result<(), ValidationError> {
+        if day < booking.start || day > booking.start + max {
+            return Err(ValidationError::new("out_of_booking"));
+        }
+        Ok(())
+    }
+
+    #[derive(Debug, Validate)]
+    struct Booking {
+        start: u32,
+        #[validate(custom(function = "after_start", with_self))]
+        end: u32,
+        #[validate(custom(function = "after_start", with_self, message = "too early"))]
+        checkout: Option<u32>,
+        #[validate(each(custom(function = "within_days", with_self, args(7))))]
+        meals: Vec<u32>,
+    }
+
+    let booking = Booking { start: 10, end: 12, checkout: Some(12), meals: vec![10, 17] };
+    assert!(booking.validate().is_ok());
+
+    let booking = Booking { start: 10, end: 9, checkout: Some(10), meals: vec![11, 18] };
+    let err = booking.validate().unwrap_err();
+    let errs = err.errors();
+    assert_eq!(errs.len(), 3);
+    let field_errs = err.field_errors();
+    assert_eq!(field_errs["end"][0].code, "before_start");
+    assert_eq!(field_errs["end"][0].params["start"], 10);
+    assert_eq!(field_errs["end"][0].params["value"], 9);
+    assert_eq!(field_errs["checkout"][0].clone().message.unwrap(), "too early");
+    match errs["meals"] {
+        validator::ValidationErrorsKind::List(ref list) => {
+            assert_eq!(list.keys().copied().collect::<Vec<_>>(), vec![1]);
+            assert_eq!(
+                list[&1].field_errors()["__all__"][0].params["args"],
+                serde_json::json!([7])
+            );
+        }
+        _ => panic!("Expected the errors of the elements"),
+    }
+}
+
+#[test]
+fn validators_after_a_custom_fn_returning_validation_errors_go_under_all() {
+    fn validate_parts(_: &str) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+        errors.add("first", ValidationError::new("missing"));
+        Err(errors)
+    }
+
+    #[derive(Debug, Validate)]
+    struct TestStruct {
+        #[validate(custom = "validate_parts", length(min = 5))]
+        name: String,
+    }
+
+    let err = TestStruct { name: "ab".to_string() }.validate().unwrap_err();
+    match err.errors()["name"] {
+        ValidationErrorsKind::Struct(ref name) => {
+            assert_eq!(name.field_errors()["first"][0].code, "missing");
+            assert_eq!(name.field_errors()["__all__"][0].code, "length");
+        }
+        ref kind => panic!("Expected nested errors, got {:?}", kind),
+    }
+    assert!(TestStruct { name: "ab".to_string() }.validate_fast().is_err());
+}
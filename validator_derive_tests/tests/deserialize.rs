@@ -0,0 +1,77 @@
+use serde::Deserialize;
+use validator::{Validate, ValidationErrorsKind};
+
+#[derive(Debug, Deserialize, Validate)]
+struct Contact {
+    #[validate(email)]
+    email: String,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+struct SignupData {
+    #[validate(length(min = 1))]
+    #[serde(rename = "firstName")]
+    first_name: String,
+    #[validate(range(min = 18))]
+    age: u8,
+    #[validate]
+    contacts: Vec<Contact>,
+}
+
+#[test]
+fn can_deserialize_and_validate() {
+    let data: SignupData = validator::from_json(
+        r#"{"firstName": "Bob", "age": 20, "contacts": [{"email": "bob@bob.com"}]}"#,
+    )
+    .unwrap();
+    assert_eq!(data.first_name, "Bob");
+}
+
+#[test]
+fn returns_validation_errors() {
+    let errs = validator::from_json::<SignupData>(
+        r#"{"firstName": "", "age": 20, "contacts": [{"email": "bob"}]}"#,
+    )
+    .unwrap_err();
+    assert_eq!(errs.errors().len(), 2);
+    assert_eq!(errs.field_errors()["firstName"][0].code, "length");
+}
+
+#[test]
+fn deserialization_errors_use_the_same_format() {
+    let errs = validator::from_json::<SignupData>(
+        r#"{"firstName": "Bob", "age": 300, "contacts": [{"email": "bob@bob.com"}, {}]}"#,
+    )
+    .unwrap_err();
+    // Deserialization stops at the first error
+    assert_eq!(errs.errors().len(), 1);
+    assert_eq!(errs.field_errors()["age"][0].code, "type");
+
+    let errs = validator::from_json::<SignupData>(
+        r#"{"firstName": "Bob", "age": 20, "contacts": [{"email": "bob@bob.com"}, {}]}"#,
+    )
+    .unwrap_err();
+    if let ValidationErrorsKind::List(ref errs) = errs.errors()["contacts"] {
+        assert_eq!(errs[&1].field_errors()["email"][0].code, "missing");
+    } else {
+        panic!("Expected list validation errors");
+    }
+}
+
+#[test]
+fn renamed_fields_use_the_serialized_name_like_validate() {
+    let errs = validator::from_json::<SignupData>(r#"{"age": 20, "contacts": []}"#).unwrap_err();
+    assert_eq!(errs.field_errors()["firstName"][0].code, "missing");
+}
+
+#[test]
+fn can_use_any_deserializer() {
+    let value = serde_json::json!({"firstName": "Bob", "age": "20", "contacts": []});
+    let errs = validator::from_deserializer::<_, SignupData>(value).unwrap_err();
+    let err = &errs.field_errors()["age"][0];
+    assert_eq!(err.code, "type");
+    assert_eq!(err.message.as_ref().unwrap(), "invalid type: string \"20\", expected u8");
+
+    let value = serde_json::json!({"firstName": "Bob", "age": 20, "contacts": []});
+    assert!(validator::from_value::<SignupData>(value).is_ok());
+}
@@ -0,0 +1,183 @@
+use std::collections::{BTreeMap, HashSet};
+
+use validator::{Validate, ValidationError, ValidationErrorsKind};
+
+fn validate_not_reserved(tag: &str) -> Result<(), ValidationError> {
+    if tag == "admin" {
+        return Err(ValidationError::new("reserved"));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn can_validate_each_element_ok() {
+    #[derive(Debug, Validate)]
+    struct TestStruct {
+        #[validate(each(email))]
+        emails: Vec<String>,
+        #[validate(each(length(max = 5)))]
+        tags: HashSet<String>,
+        #[validate(each(range(min = 1, max = 10)))]
+        scores: BTreeMap<String, u32>,
+    }
+
+    let mut tags = HashSet::new();
+    tags.insert("rust".to_string());
+    let mut scores = BTreeMap::new();
+    scores.insert("bob".to_string(), 5);
+    let s = TestStruct { emails: vec!["bob@bob.com".to_string()], tags, scores };
+
+    assert!(s.validate().is_ok());
+}
+
+#[test]
+fn each_errors_are_keyed_by_index() {
+    #[derive(Debug, Validate)]
+    struct TestStruct {
+        #[validate(each(email, length(max = 12)))]
+        emails: Vec<String>,
+    }
+
+    let s = TestStruct {
+        emails: vec!["bob@bob.com".to_string(), "bob".to_string(), "alice@alice.com".to_string()],
+    };
+    let res = s.validate();
+    assert!(res.is_err());
+    let err = res.unwrap_err();
+    let errs = err.errors();
+    assert_eq!(errs.len(), 1);
+    if let ValidationErrorsKind::List(ref errs) = errs["emails"] {
+        assert_eq!(errs.len(), 2);
+        let first = errs[&1].field_errors();
+        assert_eq!(first["__all__"].len(), 1);
+        assert_eq!(first["__all__"][0].code, "email");
+        assert_eq!(first["__all__"][0].params["value"], "bob");
+        let second = errs[&2].field_errors();
+        assert_eq!(second["__all__"].len(), 1);
+        assert_eq!(second["__all__"][0].code, "length");
+        assert_eq!(second["__all__"][0].params["max"], 12);
+    } else {
+        panic!("Expected list validation errors");
+    }
+}
+
+#[test]
+fn can_validate_each_element_of_option_vec() {
+    #[derive(Debug, Validate)]
+    struct TestStruct {
+        #[validate(each(custom = "validate_not_reserved", length(min = 1)))]
+        tags: Option<Vec<String>>,
+    }
+
+    let s = TestStruct { tags: None };
+    assert!(s.validate().is_ok());
+
+    let s = TestStruct { tags: Some(vec!["rust".to_string(), "admin".to_string()]) };
+    let res = s.validate();
+    assert!(res.is_err());
+    let err = res.unwrap_err();
+    let errs = err.errors();
+    if let ValidationErrorsKind::List(ref errs) = errs["tags"] {
+        assert_eq!(errs.len(), 1);
+        let errs = errs[&1].field_errors();
+        assert_eq!(errs["__all__"][0].code, "reserved");
+        assert_eq!(errs["__all__"][0].params["value"], "admin");
+    } else {
+        panic!("Expected list validation errors");
+    }
+}
+
+#[test]
+fn can_validate_each_optional_element() {
+    #[derive(Debug, Validate)]
+    struct TestStruct<'a> {
+        #[validate(each(url))]
+        sites: &'a [Option<String>],
+    }
+
+    let sites = vec![None, Some("http://hello.com".to_string()), Some("hello".to_string())];
+    let s = TestStruct { sites: &sites };
+    let res = s.validate();
+    assert!(res.is_err());
+    let err = res.unwrap_err();
+    let errs = err.errors();
+    if let ValidationErrorsKind::List(ref errs) = errs["sites"] {
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[&2].field_errors()["__all__"][0].code, "url");
+    } else {
+        panic!("Expected list validation errors");
+    }
+}
+
+#[test]
+fn field_validations_take_priority_over_each() {
+    #[derive(Debug, Validate)]
+    struct TestStruct {
+        #[validate(length(min = 3), each(range(max = 10)))]
+        values: Vec<i32>,
+    }
+
+    let s = TestStruct { values: vec![11] };
+    let res = s.validate();
+    assert!(res.is_err());
+    let err = res.unwrap_err();
+    let errs = err.errors();
+    if let ValidationErrorsKind::Field(ref errs) = errs["values"] {
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].code, "length");
+    } else {
+        panic!("Expected field validation errors");
+    }
+}
+
+#[test]
+fn can_specify_code_and_message_for_each() {
+    #[derive(Debug, Validate)]
+    struct TestStruct {
+        #[validate(each(range(min = 0, code = "negative", message = "oops")))]
+        values: Vec<f64>,
+    }
+
+    let s = TestStruct { values: vec![-1.0] };
+    let res = s.validate();
+    assert!(res.is_err());
+    let err = res.unwrap_err();
+    let errs = err.errors();
+    if let ValidationErrorsKind::List(ref errs) = errs["values"] {
+        let errs = errs[&0].field_errors();
+        assert_eq!(errs["__all__"][0].code, "negative");
+        assert_eq!(errs["__all__"][0].clone().message.unwrap(), "oops");
+    } else {
+        panic!("Expected list validation errors");
+    }
+}
+
+#[test]
+fn each_errors_on_maps_are_keyed_by_key() {
+    #[derive(Debug, Validate)]
+    struct TestStruct {
+        #[validate(each(range(min = 1, max = 10)))]
+        scores: BTreeMap<u32, u32>,
+    }
+
+    let mut scores = BTreeMap::new();
+    scores.insert(7, 5);
+    scores.insert(12, 20);
+    let s = TestStruct { scores };
+    for res in &[s.validate(), s.validate_fast()] {
+        let errs = res.as_ref().unwrap_err().errors();
+        if let ValidationErrorsKind::Struct(ref errs) = errs["scores"] {
+            assert_eq!(errs.errors().len(), 1);
+            if let ValidationErrorsKind::Struct(ref errs) = errs.errors()["12"] {
+                let errs = errs.field_errors();
+                assert_eq!(errs["__all__"][0].code, "range");
+                assert_eq!(errs["__all__"][0].params["value"], 20);
+            } else {
+                panic!("Expected struct validation errors");
+            }
+        } else {
+            panic!("Expected struct validation errors");
+        }
+    }
+}
@@ -0,0 +1,121 @@
+use serde_json::json;
+use validator::{Validate, ValidationError, ValidationErrors, ValidationErrorsKind};
+
+#[derive(Debug, Validate)]
+struct Address {
+    #[validate(length(min = 1))]
+    city: String,
+}
+
+// Implemented by hand, with the same errors the derive would give
+struct User {
+    name: String,
+    address: Address,
+}
+
+impl Validate for User {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+        if self.name.len() < 2 {
+            errors.add(
+                "name",
+                ValidationError::new("length")
+                    .with_message(format!("`{}` is too short", self.name))
+                    .with_param("min", &2)
+                    .with_param("value", self.name.as_str()),
+            );
+        }
+        if let Err(address) = self.address.validate() {
+            errors.add_nested("address", ValidationErrorsKind::Struct(Box::new(address)));
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+#[test]
+fn can_build_errors_by_hand() {
+    let user = User { name: "b".to_string(), address: Address { city: String::new() } };
+    let errors = user.validate().unwrap_err();
+
+    let name = &errors.field_errors()["name"][0];
+    assert_eq!(name.code, "length");
+    assert_eq!(name.message.as_deref(), Some("`b` is too short"));
+    assert_eq!(name.params["min"], json!(2));
+    assert_eq!(name.params["value"], json!("b"));
+
+    match errors.errors()["address"] {
+        ValidationErrorsKind::Struct(ref errors) => {
+            assert_eq!(errors.field_errors()["city"][0].code, "length")
+        }
+        ref kind => panic!("Expected struct errors, got {:?}", kind),
+    }
+}
+
+#[test]
+fn can_merge_errors() {
+    let mut errors: ValidationErrors =
+        vec![("name", ValidationError::new("length")), ("mail", ValidationError::new("email"))]
+            .into_iter()
+            .collect();
+
+    let mut other = ValidationErrors::new();
+    other.add("name", ValidationError::new("reserved"));
+    other.add(String::from("age"), ValidationError::new("range"));
+    errors.merge_with(other);
+
+    let codes = |field: &str| {
+        errors.field_errors()[field].iter().map(|e| e.code.to_string()).collect::<Vec<_>>()
+    };
+    assert_eq!(codes("name"), vec!["length", "reserved"]);
+    assert_eq!(codes("mail"), vec!["email"]);
+    assert_eq!(codes("age"), vec!["range"]);
+}
+
+#[test]
+fn merges_nested_errors_recursively() {
+    let address = |field: &'static str| {
+        let mut errors = ValidationErrors::new();
+        errors.add(field, ValidationError::new("length"));
+        let list = vec![(0, Box::new(errors))].into_iter().collect();
+        let mut errors = ValidationErrors::new();
+        errors.add_nested("addresses", ValidationErrorsKind::List(list));
+        errors
+    };
+
+    let errors = vec![address("city"), address("zip")].into_iter().collect::<ValidationErrors>();
+    match errors.errors()["addresses"] {
+        ValidationErrorsKind::List(ref list) => {
+            let mut fields = list[&0].field_errors().keys().cloned().collect::<Vec<_>>();
+            fields.sort();
+            assert_eq!(fields, vec!["city", "zip"]);
+        }
+        ref kind => panic!("Expected a list of errors, got {:?}", kind),
+    }
+}
+
+#[test]
+fn merges_errors_of_different_kinds_as_nested_errors() {
+    let mut errors = ValidationErrors::new();
+    errors.add("address", ValidationError::new("required"));
+    let mut nested = ValidationErrors::new();
+    nested.add("city", ValidationError::new("length"));
+    let mut other = ValidationErrors::new();
+    other.add_nested("address", ValidationErrorsKind::Struct(Box::new(nested)));
+    let list = vec![(1, Box::new(other.clone()))].into_iter().collect();
+    let mut elements = ValidationErrors::new();
+    elements.add_nested("address", ValidationErrorsKind::List(list));
+
+    let errors = vec![errors, other, elements].into_iter().collect::<ValidationErrors>();
+    match errors.errors()["address"] {
+        ValidationErrorsKind::Struct(ref address) => {
+            assert_eq!(address.field_errors()["__all__"][0].code, "required");
+            assert_eq!(address.field_errors()["city"][0].code, "length");
+            assert!(address.errors().contains_key("1"));
+        }
+        ref kind => panic!("Expected the errors of a struct, got {:?}", kind),
+    }
+}
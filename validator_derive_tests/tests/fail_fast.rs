@@ -0,0 +1,122 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+use validator::{Validate, ValidationError, ValidationErrors, ValidationErrorsKind};
+
+#[derive(Debug, Validate)]
+struct Item {
+    #[validate(length(min = 1))]
+    name: String,
+    #[validate(range(min = 1))]
+    quantity: u32,
+}
+
+#[derive(Debug, Validate)]
+struct Order {
+    #[validate(email)]
+    mail: String,
+    #[validate(length(max = 3), custom = "not_empty")]
+    code: String,
+    #[validate]
+    items: Vec<Item>,
+    #[validate(each(length(max = 5)))]
+    tags: Vec<String>,
+}
+
+fn not_empty(value: &str) -> Result<(), ValidationError> {
+    if value.is_empty() {
+        return Err(ValidationError::new("empty"));
+    }
+    Ok(())
+}
+
+fn field_codes(errors: &ValidationErrors) -> HashMap<Cow<'static, str>, Vec<String>> {
+    errors
+        .field_errors()
+        .into_iter()
+        .map(|(k, v)| (k, v.iter().map(|e| e.code.to_string()).collect()))
+        .collect()
+}
+
+fn item(name: &str, quantity: u32) -> Item {
+    Item { name: name.to_string(), quantity }
+}
+
+#[test]
+fn valid_struct_passes() {
+    let order = Order {
+        mail: "bob@bob.com".to_string(),
+        code: "ABC".to_string(),
+        items: vec![item("pen", 1)],
+        tags: vec!["new".to_string()],
+    };
+    assert!(order.validate_fast().is_ok());
+}
+
+#[test]
+fn returns_only_first_field_error() {
+    let order = Order {
+        mail: "bob".to_string(),
+        code: "ABCDEF".to_string(),
+        items: vec![item("", 0)],
+        tags: vec![],
+    };
+    assert_eq!(order.validate().unwrap_err().errors().len(), 3);
+
+    let errors = order.validate_fast().unwrap_err();
+    let mut expected = HashMap::new();
+    expected.insert(Cow::from("mail"), vec!["email".to_string()]);
+    assert_eq!(field_codes(&errors), expected);
+}
+
+#[test]
+fn stops_at_first_validator_of_a_field() {
+    let order = Order {
+        mail: "bob@bob.com".to_string(),
+        code: "".to_string(),
+        items: vec![],
+        tags: vec![],
+    };
+    let errors = order.validate_fast().unwrap_err();
+    assert_eq!(errors.field_errors()["code"][0].code, "empty");
+
+    let order = Order {
+        mail: "bob@bob.com".to_string(),
+        code: "ABCDEF".to_string(),
+        items: vec![],
+        tags: vec![],
+    };
+    let errors = order.validate_fast().unwrap_err();
+    assert_eq!(errors.field_errors()["code"].len(), 1);
+    assert_eq!(errors.field_errors()["code"][0].code, "length");
+}
+
+#[test]
+fn stops_at_first_invalid_element() {
+    let order = Order {
+        mail: "bob@bob.com".to_string(),
+        code: "ABC".to_string(),
+        items: vec![item("pen", 1), item("", 0), item("", 0)],
+        tags: vec!["a".to_string(), "too long".to_string(), "too long".to_string()],
+    };
+
+    let errors = order.validate_fast().unwrap_err();
+    assert_eq!(errors.errors().len(), 1);
+    match errors.errors()["items"] {
+        ValidationErrorsKind::List(ref list) => {
+            assert_eq!(list.keys().collect::<Vec<_>>(), vec![&1]);
+            assert_eq!(field_codes(&list[&1]).len(), 1);
+            assert_eq!(field_codes(&list[&1])["name"], vec!["length".to_string()]);
+        }
+        ref kind => panic!("Expected a list of errors, got {:?}", kind),
+    }
+
+    let order = Order { items: vec![], ..order };
+    let errors = order.validate_fast().unwrap_err();
+    match errors.errors()["tags"] {
+        ValidationErrorsKind::List(ref list) => {
+            assert_eq!(list.keys().collect::<Vec<_>>(), vec![&1]);
+        }
+        ref kind => panic!("Expected a list of errors, got {:?}", kind),
+    }
+}
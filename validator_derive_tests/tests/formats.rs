@@ -0,0 +1,47 @@
+use serde::Deserialize;
+use serde_json::json;
+use validator::formats::{json_api_errors, json_pointers, ProblemDetails};
+use validator::{Rule, RuleSet, Validate, ValidationErrors};
+
+#[derive(Debug, Deserialize, Validate)]
+struct Post {
+    #[validate(length(min = 1))]
+    title: String,
+    #[validate(each(email))]
+    tags: Vec<String>,
+}
+
+fn pointers(errors: &ValidationErrors) -> Vec<String> {
+    json_pointers(errors).into_iter().map(|(pointer, _)| pointer).collect()
+}
+
+#[test]
+fn each_errors_point_to_the_elements() {
+    let post = Post { title: String::new(), tags: vec!["a@b.c".to_string(), "bob".to_string()] };
+    let errors = post.validate().unwrap_err();
+    assert_eq!(pointers(&errors), vec!["/tags/1", "/title"]);
+
+    let problem = serde_json::to_value(ProblemDetails::new(&errors)).unwrap();
+    assert_eq!(problem["errors"][0]["pointer"], "/tags/1");
+    let errors = serde_json::to_value(json_api_errors(&errors, "/data/attributes")).unwrap();
+    assert_eq!(errors[0]["source"]["pointer"], "/data/attributes/tags/1");
+}
+
+#[test]
+fn rule_set_array_errors_point_to_the_elements() {
+    let rules = RuleSet::new().field("tags[]", vec![Rule::email()]);
+    let errors = rules.validate(&json!({"tags": ["a@b.c", "bob"]})).unwrap_err();
+    assert_eq!(pointers(&errors), vec!["/tags/1"]);
+}
+
+#[test]
+fn deserialization_errors_point_to_the_elements() {
+    #[derive(Debug, Deserialize, Validate)]
+    #[allow(dead_code)]
+    struct Scores {
+        values: Vec<u32>,
+    }
+
+    let errors = validator::from_json::<Scores>(r#"{"values": [1, "a"]}"#).unwrap_err();
+    assert_eq!(pointers(&errors), vec!["/values/1"]);
+}
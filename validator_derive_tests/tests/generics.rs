@@ -0,0 +1,100 @@
+use std::fmt::Debug;
+
+use validator::{Validate, ValidationErrorsKind};
+
+#[derive(Debug, Validate)]
+struct Item {
+    #[validate(length(min = 1))]
+    name: String,
+}
+
+#[derive(Debug, Validate)]
+struct Page<T> {
+    #[validate(nested)]
+    items: Vec<T>,
+    #[validate(range(max = 100))]
+    limit: u32,
+}
+
+#[test]
+fn can_validate_generic_nested_vec() {
+    let page = Page { items: vec![Item { name: "a".to_string() }], limit: 10 };
+    assert!(page.validate().is_ok());
+
+    let page = Page {
+        items: vec![Item { name: "a".to_string() }, Item { name: String::new() }],
+        limit: 101,
+    };
+    let res = page.validate();
+    assert!(res.is_err());
+    let err = res.unwrap_err();
+    let errs = err.errors();
+    assert_eq!(errs.len(), 2);
+    assert!(matches!(errs["limit"], ValidationErrorsKind::Field(_)));
+    if let ValidationErrorsKind::List(ref errs) = errs["items"] {
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[&1].field_errors()["name"][0].code, "length");
+    } else {
+        panic!("Expected list validation errors");
+    }
+}
+
+#[test]
+fn can_validate_generic_optional_nested_field() {
+    #[derive(Debug, Validate)]
+    struct Wrapper<'a, T, U, L: Debug> {
+        #[validate]
+        inner: Option<T>,
+        #[validate]
+        reference: &'a U,
+        // Not validated so it doesn't need to implement `Validate`
+        label: L,
+    }
+
+    let item = Item { name: "a".to_string() };
+    let s = Wrapper { inner: Some(Item { name: String::new() }), reference: &item, label: "label" };
+    assert_eq!(s.label, "label");
+    let res = s.validate();
+    assert!(res.is_err());
+    let err = res.unwrap_err();
+    assert!(matches!(err.errors()["inner"], ValidationErrorsKind::Struct(_)));
+
+    let s = Wrapper::<Item, Item, ()> { inner: None, reference: &item, label: () };
+    assert!(s.validate().is_ok());
+}
+
+#[test]
+fn can_override_generic_bounds() {
+    trait Resource {
+        type Item: Validate;
+    }
+
+    struct Items;
+
+    impl Resource for Items {
+        type Item = Item;
+    }
+
+    #[derive(Validate)]
+    #[validate(bound = "R::Item: Validate")]
+    struct Listing<R: Resource> {
+        #[validate(nested)]
+        entries: Vec<R::Item>,
+    }
+
+    let listing = Listing::<Items> { entries: vec![Item { name: String::new() }] };
+    assert!(listing.validate().is_err());
+}
+
+#[test]
+fn can_remove_generic_bounds() {
+    #[derive(Debug, Validate)]
+    #[validate(bound = "")]
+    struct Single<T: Validate> {
+        #[validate]
+        value: T,
+    }
+
+    let s = Single { value: Item { name: "a".to_string() } };
+    assert!(s.validate().is_ok());
+}
@@ -0,0 +1,278 @@
+#[macro_use]
+extern crate lazy_static;
+
+use std::collections::HashMap;
+
+use regex::Regex;
+use serde::Deserialize;
+use serde_json::json;
+use validator::{ToJsonSchema, Validate};
+
+lazy_static! {
+    static ref RE_USERNAME: Regex = Regex::new(r"^[a-z0-9_]+$").unwrap();
+}
+
+const MAX_TAGS: usize = 5;
+
+#[derive(Debug, Deserialize, Validate)]
+struct Address {
+    #[validate(length(min = 1))]
+    city: String,
+    #[validate(length(equal = 5))]
+    zip: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+struct Signup {
+    #[serde(rename = "userName")]
+    #[validate(length(min = 3, max = 20), regex = "RE_USERNAME")]
+    user_name: String,
+    #[validate(email)]
+    mail: String,
+    #[validate(url)]
+    site: Option<String>,
+    #[validate(range(min = 18, max = 20.5))]
+    age: u8,
+    #[validate(length(max = "MAX_TAGS"))]
+    tags: Vec<String>,
+    #[validate(contains = "a.b")]
+    bio: String,
+    #[validate(length(min = 1, max = 10))]
+    comments: Option<Vec<String>>,
+    #[validate(required)]
+    consent: Option<bool>,
+    #[validate]
+    address: Address,
+    #[serde(rename = "otherAddresses")]
+    #[validate]
+    other_addresses: Vec<Address>,
+    #[validate(length(min = 1))]
+    notes: HashMap<String, String>,
+}
+
+#[test]
+fn generates_schema_from_constraints() {
+    assert_eq!(
+        Signup::json_schema(),
+        json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "title": "Signup",
+            "type": "object",
+            "properties": {
+                "userName": {
+                    "type": "string",
+                    "minLength": 3,
+                    "maxLength": 20,
+                    "pattern": "^[a-z0-9_]+$",
+                },
+                "mail": {"type": "string", "format": "email"},
+                "site": {"type": ["string", "null"], "format": "uri"},
+                "age": {"type": "integer", "minimum": 18, "maximum": 20.5},
+                "tags": {
+                    "type": "array",
+                    "items": {"type": "string"},
+                    "maxItems": 5,
+                },
+                "bio": {"type": "string", "pattern": "a\\.b"},
+                "comments": {
+                    "type": ["array", "null"],
+                    "items": {"type": "string"},
+                    "minItems": 1,
+                    "maxItems": 10,
+                },
+                "consent": {"type": ["boolean", "null"]},
+                "address": {"$ref": "#/$defs/Address"},
+                "otherAddresses": {"type": "array", "items": {"$ref": "#/$defs/Address"}},
+                "notes": {
+                    "type": "object",
+                    "additionalProperties": {"type": "string"},
+                    "minProperties": 1,
+                },
+            },
+            "required": [
+                "userName",
+                "mail",
+                "age",
+                "tags",
+                "bio",
+                "consent",
+                "address",
+                "otherAddresses",
+                "notes",
+            ],
+            "$defs": {
+                "Address": {
+                    "type": "object",
+                    "properties": {
+                        "city": {"type": "string", "minLength": 1},
+                        "zip": {"type": ["string", "null"], "minLength": 5, "maxLength": 5},
+                    },
+                    "required": ["city"],
+                },
+            },
+        })
+    );
+}
+
+#[test]
+fn applies_each_constraints_to_elements() {
+    #[derive(Debug, Validate)]
+    struct Tags {
+        #[validate(each(length(max = 10), email))]
+        tags: Vec<String>,
+        #[validate(each(range(min = 1)))]
+        scores: HashMap<String, Option<i32>>,
+    }
+
+    assert_eq!(
+        Tags::json_schema()["properties"],
+        json!({
+            "tags": {
+                "type": "array",
+                "items": {"type": "string", "maxLength": 10, "format": "email"},
+            },
+            "scores": {
+                "type": "object",
+                "additionalProperties": {"type": ["integer", "null"], "minimum": 1},
+            },
+        })
+    );
+}
+
+#[test]
+fn optional_nested_struct_is_nullable() {
+    #[derive(Debug, Validate)]
+    struct Inner<T: Validate> {
+        #[validate]
+        value: T,
+    }
+
+    #[derive(Debug, Validate)]
+    struct Outer {
+        #[validate]
+        inner: Option<Inner<Address>>,
+        #[validate(required, length(min = 2))]
+        name: Option<String>,
+    }
+
+    let schema = Outer::json_schema();
+    assert_eq!(
+        schema["properties"]["inner"],
+        json!({"anyOf": [{"$ref": "#/$defs/Inner%3Cjson_schema::Address%3E"}, {"type": "null"}]})
+    );
+    assert_eq!(schema["properties"]["name"], json!({"type": ["string", "null"], "minLength": 2}));
+    assert_eq!(schema["required"], json!(["name"]));
+    assert_eq!(
+        schema["$defs"]["Inner<json_schema::Address>"]["properties"]["value"],
+        json!({"$ref": "#/$defs/Address"})
+    );
+}
+
+#[test]
+fn instantiations_of_a_generic_struct_have_their_own_definition() {
+    #[derive(Debug, Validate)]
+    struct Page<T: Validate> {
+        #[validate]
+        items: Vec<T>,
+    }
+
+    #[derive(Debug, Validate)]
+    struct Results {
+        #[validate]
+        addresses: Page<Address>,
+        #[validate]
+        signups: Page<Signup>,
+    }
+
+    let schema = Results::json_schema();
+    assert_eq!(
+        schema["properties"]["addresses"],
+        json!({"$ref": "#/$defs/Page%3Cjson_schema::Address%3E"})
+    );
+    assert_eq!(
+        schema["properties"]["signups"],
+        json!({"$ref": "#/$defs/Page%3Cjson_schema::Signup%3E"})
+    );
+    let defs = schema["$defs"].as_object().unwrap();
+    assert_eq!(
+        defs["Page<json_schema::Address>"]["properties"]["items"]["items"],
+        json!({"$ref": "#/$defs/Address"})
+    );
+    assert_eq!(
+        defs["Page<json_schema::Signup>"]["properties"]["items"]["items"],
+        json!({"$ref": "#/$defs/Signup"})
+    );
+}
+
+#[test]
+fn can_override_the_schema_name() {
+    mod billing {
+        use validator::Validate;
+
+        #[derive(Debug, Validate)]
+        #[validate(schema_name = "BillingAddress")]
+        pub struct Address {
+            #[validate(length(min = 1))]
+            pub iban: String,
+        }
+    }
+
+    #[derive(Debug, Validate)]
+    struct Order {
+        #[validate]
+        shipping: Address,
+        #[validate]
+        billing: billing::Address,
+    }
+
+    let schema = Order::json_schema();
+    assert_eq!(schema["properties"]["shipping"], json!({"$ref": "#/$defs/Address"}));
+    assert_eq!(schema["properties"]["billing"], json!({"$ref": "#/$defs/BillingAddress"}));
+    assert_eq!(schema["$defs"]["BillingAddress"]["required"], json!(["iban"]));
+    assert_eq!(billing::Address::json_schema()["title"], "BillingAddress");
+}
+
+#[test]
+fn not_empty_and_not_blank_set_a_minimum_and_a_pattern() {
+    #[derive(Debug, Validate)]
+    struct Post {
+        #[validate(not_blank, not_empty)]
+        title: String,
+        #[validate(length(min = 2), not_empty)]
+        tags: Vec<String>,
+    }
+
+    let schema = Post::json_schema();
+    assert_eq!(
+        schema["properties"]["title"],
+        json!({"type": "string", "pattern": "\\S", "minLength": 1})
+    );
+    assert_eq!(
+        schema["properties"]["tags"],
+        json!({"type": "array", "items": {"type": "string"}, "minItems": 2})
+    );
+}
+
+#[test]
+fn presence_rules_require_the_fields_to_have_values() {
+    #[derive(Debug, Validate)]
+    #[validate(at_least_one_of("email", "phone"))]
+    #[validate(mutually_exclusive("duration", "end"))]
+    struct Reminder {
+        email: Option<String>,
+        phone: Option<String>,
+        duration: Option<u32>,
+        end: Option<String>,
+    }
+
+    let has_values = |fields: &[&str]| {
+        let properties = fields
+            .iter()
+            .map(|field| (field.to_string(), json!({"not": {"type": "null"}})))
+            .collect::<serde_json::Map<_, _>>();
+        json!({"required": fields, "properties": properties})
+    };
+    let schema = Reminder::json_schema();
+    assert_eq!(schema["anyOf"], json!([has_values(&["email"]), has_values(&["phone"])]));
+    assert_eq!(schema["not"], json!({"anyOf": [has_values(&["duration", "end"])]}));
+}
@@ -1,7 +1,8 @@
 use serde::Serialize;
 use std::{borrow::Cow, collections::HashMap};
 use validator::{
-    validate_length, Validate, ValidationError, ValidationErrors, ValidationErrorsKind,
+    validate_length, TaggedValidationErrors, Validate, ValidationError, ValidationErrors,
+    ValidationErrorsKind,
 };
 
 #[derive(Debug, Validate)]
@@ -338,19 +339,14 @@ fn test_field_validation_errors_replaced_with_nested_validations_fails() {
 }
 
 #[test]
-#[should_panic(
-    expected = "Attempt to add field validation to a non-Field ValidationErrorsKind instance"
-)]
-#[allow(unused)]
-fn test_field_validations_evaluated_after_nested_validations_fails() {
+fn test_field_validations_evaluated_after_nested_validations_go_under_all() {
     #[derive(Debug)]
     struct ParentWithStructValidationsFirst {
         child: Vec<Child>,
     }
 
     impl Validate for ParentWithStructValidationsFirst {
-        // Evaluating fields after their nested structs should fail because field
-        // validations are expected to take priority over nested struct validations
+        // Evaluating fields after their nested structs puts the field errors in the nested errors
         #[allow(unused_mut)]
         fn validate(&self) -> Result<(), ValidationErrors> {
             // First validate the nested vector of structs:
@@ -383,12 +379,95 @@ fn test_field_validations_evaluated_after_nested_validations_fails() {
     }
 
     let instance = ParentWithStructValidationsFirst { child: vec![Child { value: String::new() }] };
-    let res = instance.validate();
+    let errors = instance.validate().unwrap_err();
+    match errors.errors()["child"] {
+        ValidationErrorsKind::Struct(ref child) => {
+            assert_eq!(child.field_errors()["__all__"][0].code, "length");
+            assert!(child.errors().contains_key("0"));
+        }
+        ref kind => panic!("Expected nested errors, got {:?}", kind),
+    }
+}
+
+#[test]
+fn test_errors_round_trip_through_json() {
+    let root = Root {
+        value: String::new(),
+        a: &A { value: String::new(), b: B { value: String::new() } },
+    };
+    let errors = root.validate().unwrap_err();
+    let json = serde_json::to_string(&errors).unwrap();
+    assert_eq!(serde_json::from_str::<ValidationErrors>(&json).unwrap(), errors);
+
+    let instance = ParentWithVectorOfChildren {
+        child: vec![
+            Child { value: "valid".to_string() },
+            Child { value: String::new() },
+            Child { value: String::new() },
+        ],
+    };
+    let errors = instance.validate().unwrap_err();
+    let value = serde_json::to_value(&errors).unwrap();
+    assert_eq!(value["child"]["1"]["value"][0]["code"], "length");
+    // The untagged representation can't tell a list from a struct
+    let parsed = serde_json::from_value::<ValidationErrors>(value).unwrap();
+    match parsed.errors()["child"] {
+        ValidationErrorsKind::Struct(ref errors) => assert!(errors.errors().contains_key("1")),
+        ref kind => panic!("Expected a struct of errors, got {:?}", kind),
+    }
+
+    let value = serde_json::to_value(TaggedValidationErrors::from(errors.clone())).unwrap();
+    assert_eq!(value["child"]["list"]["1"]["value"]["field"][0]["code"], "length");
+    let parsed = serde_json::from_value::<TaggedValidationErrors>(value).unwrap();
+    assert_eq!(parsed.0, errors);
+    match parsed.0.errors()["child"] {
+        ValidationErrorsKind::List(ref list) => {
+            assert_eq!(list.keys().cloned().collect::<Vec<_>>(), vec![1, 2])
+        }
+        ref kind => panic!("Expected a list of errors, got {:?}", kind),
+    }
+}
+
+#[test]
+fn test_tagged_errors_round_trip_with_numeric_struct_keys() {
+    let mut form = ValidationErrors::new();
+    form.add("0", ValidationError::new("required"));
+    form.add("1", ValidationError::new("email"));
+    let mut field = ValidationErrors::new();
+    field.add("value", ValidationError::new("length"));
+    form.add_nested("2", ValidationErrorsKind::Struct(Box::new(field)));
+    let mut errors = ValidationErrors::new();
+    errors.add_nested("form", ValidationErrorsKind::Struct(Box::new(form)));
+
+    let json = serde_json::to_string(&TaggedValidationErrors::from(errors.clone())).unwrap();
+    let parsed = serde_json::from_str::<TaggedValidationErrors>(&json).unwrap();
+    assert_eq!(parsed.0, errors);
+}
+
+#[test]
+fn test_errors_with_runtime_keys() {
+    let mut errors = ValidationErrors::new();
+    for name in &["first", "second"] {
+        errors.add(format!("{}_name", name), ValidationError::new("length"));
+    }
+    assert!(ValidationErrors::has_error(&Err(errors.clone()), "first_name"));
+
+    let result = ValidationErrors::merge(Ok(()), format!("item_{}", 1), Err(errors.clone()));
+    let element = ValidationErrors::merge(Ok(()), String::from("items"), Err(errors));
+    let result = ValidationErrors::merge_all(result, String::from("items"), vec![Ok(()), element]);
+    let errors = result.unwrap_err();
+    match errors.errors()["item_1"] {
+        ValidationErrorsKind::Struct(ref errors) => {
+            assert_eq!(errors.field_errors()["second_name"][0].code, "length")
+        }
+        ref kind => panic!("Expected struct errors, got {:?}", kind),
+    }
+    assert!(errors.errors().contains_key("items"));
 }
 
 fn unwrap_map<F>(errors: &ValidationErrors, f: F)
 where
-    F: FnOnce(HashMap<&'static str, ValidationErrorsKind>),
+    F: FnOnce(HashMap<Cow<'static, str>, ValidationErrorsKind>),
 {
     let errors = errors.clone();
     f(errors.errors().clone());
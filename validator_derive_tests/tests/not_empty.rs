@@ -0,0 +1,62 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+use validator::{Validate, ValidationErrorsKind};
+
+#[test]
+fn can_validate_not_blank() {
+    #[derive(Debug, Validate)]
+    struct TestStruct<'a> {
+        #[validate(not_blank)]
+        name: String,
+        #[validate(not_blank(message = "Say something"))]
+        comment: Option<Cow<'a, str>>,
+        #[validate(each(not_blank))]
+        tags: Vec<&'a str>,
+    }
+
+    let s = TestStruct { name: " Bob ".to_string(), comment: None, tags: vec!["a"] };
+    assert!(s.validate().is_ok());
+
+    let s = TestStruct {
+        name: "\u{00A0}\t ".to_string(),
+        comment: Some(Cow::from("\u{3000}")),
+        tags: vec!["a", "\n"],
+    };
+    let err = s.validate().unwrap_err();
+    let errs = err.field_errors();
+    assert_eq!(errs["name"].len(), 1);
+    assert_eq!(errs["name"][0].code, "not_blank");
+    assert_eq!(errs["name"][0].params["value"], "\u{00A0}\t ");
+    assert_eq!(errs["comment"][0].clone().message.unwrap(), "Say something");
+    match err.errors()["tags"] {
+        ValidationErrorsKind::List(ref list) => {
+            assert_eq!(list.keys().copied().collect::<Vec<_>>(), vec![1]);
+        }
+        _ => panic!("Expected the errors of the elements"),
+    }
+}
+
+#[test]
+fn can_validate_not_empty() {
+    #[derive(Debug, Validate)]
+    struct TestStruct {
+        #[validate(not_empty)]
+        name: String,
+        #[validate(not_empty(code = "no_items"))]
+        items: Vec<u32>,
+        #[validate(not_empty)]
+        labels: Option<HashMap<String, String>>,
+    }
+
+    let s = TestStruct { name: " ".to_string(), items: vec![1], labels: None };
+    assert!(s.validate().is_ok());
+
+    let s = TestStruct { name: String::new(), items: vec![], labels: Some(HashMap::new()) };
+    let err = s.validate().unwrap_err();
+    let errs = err.field_errors();
+    assert_eq!(errs.len(), 3);
+    assert_eq!(errs["name"][0].code, "not_empty");
+    assert_eq!(errs["items"][0].code, "no_items");
+    assert_eq!(errs["labels"][0].code, "not_empty");
+}
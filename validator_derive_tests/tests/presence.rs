@@ -0,0 +1,131 @@
+use serde::Deserialize;
+use validator::{Validate, ValidationError, ValidationErrors, ValidationErrorsKind};
+
+#[test]
+fn can_validate_at_least_one_of() {
+    #[derive(Debug, Validate)]
+    #[validate(at_least_one_of("email", "phone"))]
+    struct TestStruct {
+        email: Option<String>,
+        phone: Option<String>,
+    }
+
+    assert!(TestStruct { email: Some("a@b.c".to_string()), phone: None }.validate().is_ok());
+    assert!(TestStruct { email: Some("a@b.c".to_string()), phone: Some("1".to_string()) }
+        .validate()
+        .is_ok());
+
+    let err = TestStruct { email: None, phone: None }.validate().unwrap_err();
+    let errs = err.field_errors();
+    assert_eq!(errs.len(), 1);
+    assert_eq!(errs["__all__"].len(), 1);
+    assert_eq!(errs["__all__"][0].code, "at_least_one_of");
+    assert_eq!(errs["__all__"][0].params["fields"], serde_json::json!(["email", "phone"]));
+}
+
+#[test]
+fn can_validate_exactly_one_of() {
+    #[derive(Debug, Validate)]
+    #[validate(exactly_one_of("card", "iban", "paypal", code = "payment", message = "Pick one"))]
+    struct TestStruct {
+        card: Option<String>,
+        iban: Option<String>,
+        paypal: Option<Option<String>>,
+    }
+
+    assert!(TestStruct { card: None, iban: None, paypal: Some(None) }.validate().is_ok());
+
+    let err = TestStruct { card: None, iban: None, paypal: None }.validate().unwrap_err();
+    let errs = err.field_errors();
+    assert_eq!(errs["__all__"][0].code, "payment");
+    assert_eq!(errs["__all__"][0].clone().message.unwrap(), "Pick one");
+
+    let s = TestStruct { card: Some("1".to_string()), iban: Some("2".to_string()), paypal: None };
+    assert!(s.validate().is_err());
+}
+
+#[test]
+fn can_put_the_errors_on_the_fields() {
+    #[derive(Debug, Validate, Deserialize)]
+    #[validate(exactly_one_of("email", "phone", on_fields = true))]
+    #[validate(mutually_exclusive("start", "duration", "end", on_fields = true))]
+    struct TestStruct {
+        email: Option<String>,
+        #[serde(rename = "phoneNumber")]
+        phone: Option<String>,
+        start: Option<u32>,
+        duration: Option<u32>,
+        end: Option<u32>,
+    }
+
+    let s = TestStruct { email: None, phone: None, start: Some(1), duration: None, end: Some(3) };
+    let err = s.validate().unwrap_err();
+    let errs = err.field_errors();
+    let mut fields = errs.keys().map(|k| k.as_ref()).collect::<Vec<_>>();
+    fields.sort_unstable();
+    assert_eq!(fields, vec!["email", "end", "phoneNumber", "start"]);
+    assert_eq!(errs["email"][0].code, "exactly_one_of");
+    assert_eq!(
+        errs["phoneNumber"][0].params["fields"],
+        serde_json::json!(["email", "phoneNumber"])
+    );
+    assert_eq!(errs["start"][0].code, "mutually_exclusive");
+
+    let s = TestStruct {
+        email: Some("a@b.c".to_string()),
+        phone: Some("1".to_string()),
+        start: Some(1),
+        duration: None,
+        end: None,
+    };
+    let err = s.validate().unwrap_err();
+    let errs = err.field_errors();
+    assert_eq!(errs.len(), 2);
+    assert_eq!(errs["email"][0].code, "exactly_one_of");
+    assert_eq!(errs["phoneNumber"][0].code, "exactly_one_of");
+}
+
+#[test]
+fn presence_rules_stop_validate_fast() {
+    #[derive(Debug, Validate)]
+    #[validate(mutually_exclusive("start", "duration"))]
+    struct TestStruct {
+        start: Option<u32>,
+        duration: Option<u32>,
+        #[validate(range(min = 1))]
+        count: u32,
+    }
+
+    let s = TestStruct { start: Some(1), duration: Some(2), count: 1 };
+    let err = s.validate_fast().unwrap_err();
+    assert_eq!(err.field_errors()["__all__"][0].code, "mutually_exclusive");
+}
+
+#[test]
+fn errors_on_fields_go_under_all_of_nested_errors() {
+    fn validate_email(_: &str) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+        errors.add("domain", ValidationError::new("unknown"));
+        Err(errors)
+    }
+
+    #[derive(Debug, Validate)]
+    #[validate(exactly_one_of("email", "phone", on_fields = true))]
+    struct TestStruct {
+        #[validate(custom = "validate_email")]
+        email: Option<String>,
+        phone: Option<String>,
+    }
+
+    let err = TestStruct { email: Some("a@b.c".to_string()), phone: Some("123".to_string()) }
+        .validate()
+        .unwrap_err();
+    assert_eq!(err.field_errors()["phone"][0].code, "exactly_one_of");
+    match err.errors()["email"] {
+        ValidationErrorsKind::Struct(ref email) => {
+            assert_eq!(email.field_errors()["domain"][0].code, "unknown");
+            assert_eq!(email.field_errors()["__all__"][0].code, "exactly_one_of");
+        }
+        ref kind => panic!("Expected nested errors, got {:?}", kind),
+    }
+}
@@ -0,0 +1,103 @@
+use validator::Validate;
+
+#[test]
+fn can_validate_required_if() {
+    #[derive(Debug, Validate)]
+    struct Account {
+        kind: String,
+        #[validate(required_if(field = "kind", value = "business"))]
+        company_name: Option<String>,
+    }
+
+    assert!(Account { kind: "personal".to_string(), company_name: None }.validate().is_ok());
+    assert!(Account { kind: "business".to_string(), company_name: Some("Acme".to_string()) }
+        .validate()
+        .is_ok());
+
+    let err = Account { kind: "business".to_string(), company_name: None }.validate().unwrap_err();
+    let errs = err.field_errors();
+    assert_eq!(errs.len(), 1);
+    assert_eq!(errs["company_name"].len(), 1);
+    assert_eq!(errs["company_name"][0].code, "required_if");
+    assert_eq!(errs["company_name"][0].params["other"], "kind");
+    // The value of the other field isn't given, it may be sensitive
+    assert!(!errs["company_name"][0].params.contains_key("other_value"));
+}
+
+#[test]
+fn can_validate_required_unless() {
+    #[derive(Debug, Validate)]
+    struct Shipping {
+        pickup: Option<bool>,
+        #[validate(required_unless(field = "pickup", value = true, message = "Where to?"))]
+        address: Option<String>,
+        express: u8,
+        #[validate(required_unless(field = "express", value = 0, code = "phone"))]
+        phone: Option<String>,
+    }
+
+    assert!(Shipping { pickup: Some(true), address: None, express: 0, phone: None }
+        .validate()
+        .is_ok());
+
+    let err =
+        Shipping { pickup: None, address: None, express: 2, phone: None }.validate().unwrap_err();
+    let errs = err.field_errors();
+    assert_eq!(errs["address"][0].code, "required_unless");
+    assert_eq!(errs["address"][0].clone().message.unwrap(), "Where to?");
+    assert_eq!(errs["address"][0].params["other"], "pickup");
+    assert_eq!(errs["phone"][0].code, "phone");
+
+    let s = Shipping { pickup: Some(false), address: None, express: 0, phone: None };
+    assert!(s.validate().unwrap_err().field_errors().contains_key("address"));
+}
+
+#[test]
+fn can_validate_required_with_and_without() {
+    #[derive(Debug, Validate)]
+    struct Contact {
+        #[validate(required_without = "phone")]
+        email: Option<String>,
+        phone: Option<String>,
+        #[validate(required_with(field = "phone"))]
+        phone_country: Option<String>,
+    }
+
+    assert!(Contact { email: Some("a@b.c".to_string()), phone: None, phone_country: None }
+        .validate()
+        .is_ok());
+    assert!(Contact {
+        email: None,
+        phone: Some("1".to_string()),
+        phone_country: Some("FR".to_string())
+    }
+    .validate()
+    .is_ok());
+
+    let err = Contact { email: None, phone: None, phone_country: None }.validate().unwrap_err();
+    let errs = err.field_errors();
+    assert_eq!(errs.len(), 1);
+    assert_eq!(errs["email"][0].code, "required_without");
+    assert_eq!(errs["email"][0].params["other"], "phone");
+
+    let err = Contact { email: None, phone: Some("1".to_string()), phone_country: None }
+        .validate()
+        .unwrap_err();
+    let errs = err.field_errors();
+    assert_eq!(errs.len(), 1);
+    assert_eq!(errs["phone_country"][0].code, "required_with");
+}
+
+#[test]
+fn required_if_only_looks_at_optional_fields_with_a_value() {
+    #[derive(Debug, Validate)]
+    struct Account {
+        kind: Option<Option<&'static str>>,
+        #[validate(required_if(field = "kind", value = "business"))]
+        vat_number: Option<String>,
+    }
+
+    assert!(Account { kind: None, vat_number: None }.validate().is_ok());
+    assert!(Account { kind: Some(None), vat_number: None }.validate().is_ok());
+    assert!(Account { kind: Some(Some("business")), vat_number: None }.validate().is_err());
+}
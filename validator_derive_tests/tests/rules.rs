@@ -0,0 +1,69 @@
+use serde::Serialize;
+use serde_json::json;
+use validator::{Rule, RuleSet, Validate};
+
+#[derive(Debug, Serialize, Validate)]
+struct Item {
+    #[validate(length(min = 1, max = 10))]
+    name: String,
+    #[validate(range(min = 1, max = 100))]
+    quantity: u32,
+}
+
+#[derive(Debug, Serialize, Validate)]
+struct Order {
+    #[validate(email)]
+    email: String,
+    #[validate(required)]
+    reference: Option<String>,
+    #[validate]
+    item: Item,
+    #[validate(length(min = 1))]
+    #[validate]
+    items: Vec<Item>,
+    #[validate(each(url))]
+    links: Vec<String>,
+}
+
+fn order_rules() -> RuleSet {
+    RuleSet::new()
+        .field("email", vec![Rule::email()])
+        .field("reference", vec![Rule::required()])
+        .field("item.name", vec![Rule::length(Some(1), Some(10), None)])
+        .field("item.quantity", vec![Rule::range(Some(1.0), Some(100.0))])
+        .field("items", vec![Rule::length(Some(1), None, None)])
+        .field("items[].name", vec![Rule::length(Some(1), Some(10), None)])
+        .field("items[].quantity", vec![Rule::range(Some(1.0), Some(100.0))])
+        .field("links[]", vec![Rule::url()])
+}
+
+#[test]
+fn rule_set_gives_the_same_errors_as_the_derive() {
+    let order = Order {
+        email: "bob".to_string(),
+        reference: None,
+        item: Item { name: "".to_string(), quantity: 1 },
+        items: vec![
+            Item { name: "a".to_string(), quantity: 1 },
+            Item { name: "a very long name".to_string(), quantity: 0 },
+        ],
+        links: vec!["http://example.com".to_string(), "example".to_string()],
+    };
+
+    let expected = order.validate().unwrap_err();
+    let errs = order_rules().validate(&serde_json::to_value(&order).unwrap()).unwrap_err();
+    assert_eq!(errs, expected);
+}
+
+#[test]
+fn rule_set_can_validate_valid_value() {
+    let value = json!({
+        "email": "bob@bob.com",
+        "reference": "abc",
+        "item": {"name": "a", "quantity": 10},
+        "items": [{"name": "b", "quantity": 100}],
+        "links": [],
+    });
+
+    assert!(order_rules().validate(&value).is_ok());
+}
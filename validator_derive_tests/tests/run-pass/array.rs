@@ -2,7 +2,9 @@ use validator::Validate;
 
 #[derive(Validate)]
 struct Values {
+    #[validate(each(range(max = 100)))]
     values: [u8; 10],
+    bytes: [u8; 4],
 }
 
 fn main() {}
@@ -0,0 +1,121 @@
+use validator::{Sanitize, Validate};
+
+fn remove_dashes(value: &mut String) {
+    value.retain(|c| c != '-');
+}
+
+#[derive(Debug, Sanitize, Validate)]
+struct Address {
+    #[sanitizer(collapse_whitespace)]
+    #[validate(length(min = 1))]
+    street: String,
+    #[sanitizer(trim, uppercase)]
+    country: Option<String>,
+}
+
+#[derive(Debug, Sanitize, Validate)]
+struct SignupData {
+    #[sanitizer(trim, lowercase)]
+    #[validate(email)]
+    mail: String,
+    #[sanitizer(trim, custom = "remove_dashes")]
+    #[validate(length(equal = 10))]
+    phone: String,
+    #[sanitizer(nfc)]
+    name: Option<Option<String>>,
+    #[sanitizer(nested)]
+    #[validate]
+    address: Address,
+    #[sanitizer]
+    #[validate]
+    previous_addresses: Option<Vec<Address>>,
+    untouched: String,
+}
+
+fn signup_data() -> SignupData {
+    SignupData {
+        mail: "  Bob@Example.COM ".to_string(),
+        phone: "012-345-6789".to_string(),
+        name: Some(Some("e\u{0301}lise".to_string())),
+        address: Address {
+            street: " 1  Main\tStreet ".to_string(),
+            country: Some(" fr".to_string()),
+        },
+        previous_addresses: Some(vec![Address { street: "  ".to_string(), country: None }]),
+        untouched: "  Bob ".to_string(),
+    }
+}
+
+#[test]
+fn can_sanitize_fields() {
+    let mut s = signup_data();
+    s.sanitize();
+
+    assert_eq!(s.mail, "bob@example.com");
+    assert_eq!(s.phone, "0123456789");
+    assert_eq!(s.name, Some(Some("\u{00e9}lise".to_string())));
+    assert_eq!(s.untouched, "  Bob ");
+}
+
+#[test]
+fn can_sanitize_nested_fields() {
+    let mut s = signup_data();
+    s.sanitize();
+
+    assert_eq!(s.address.street, "1 Main Street");
+    assert_eq!(s.address.country, Some("FR".to_string()));
+    let previous = s.previous_addresses.as_ref().unwrap();
+    assert_eq!(previous[0].street, "");
+    assert_eq!(previous[0].country, None);
+}
+
+#[test]
+fn can_sanitize_and_validate() {
+    let mut s = signup_data();
+    assert!(s.validate().is_err());
+
+    let res = s.sanitize_and_validate();
+    assert!(res.is_err());
+    let err = res.unwrap_err();
+    let errs = err.errors();
+    // Only the empty street of the previous address is left invalid
+    assert_eq!(errs.len(), 1);
+    assert!(errs.contains_key("previous_addresses"));
+
+    s.previous_addresses = None;
+    assert!(s.sanitize_and_validate().is_ok());
+}
+
+#[test]
+fn can_sanitize_generic_nested_fields() {
+    #[derive(Debug, Sanitize)]
+    struct Page<T> {
+        #[sanitizer(nested)]
+        items: Vec<T>,
+    }
+
+    let mut page = Page { items: vec![Address { street: " a ".to_string(), country: None }] };
+    page.sanitize();
+    assert_eq!(page.items[0].street, "a");
+}
+
+macro_rules! tag_struct {
+    ($name:ident, $ty:ty) => {
+        #[derive(Debug, Sanitize, Validate)]
+        struct $name {
+            #[sanitizer(trim)]
+            #[validate(length(min = 1))]
+            tag: $ty,
+        }
+    };
+}
+
+tag_struct!(Tag, String);
+
+#[test]
+fn types_given_to_a_macro_are_sanitized() {
+    let mut tag = Tag { tag: "  rust ".to_string() };
+    tag.sanitize();
+    assert_eq!(tag.tag, "rust");
+    assert!(tag.validate().is_ok());
+}
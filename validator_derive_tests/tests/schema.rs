@@ -1,4 +1,4 @@
-use validator::{Validate, ValidationError};
+use validator::{Validate, ValidationError, ValidationErrors};
 
 #[test]
 fn can_validate_schema_fn_ok() {
@@ -8,6 +8,7 @@ fn can_validate_schema_fn_ok() {
 
     #[derive(Debug, Validate)]
     #[validate(schema(function = "valid_schema_fn"))]
+    #[allow(dead_code)]
     struct TestStruct {
         val: String,
     }
@@ -30,6 +31,7 @@ fn can_validate_multiple_schema_fn_ok() {
     #[derive(Debug, Validate)]
     #[validate(schema(function = "valid_schema_fn"))]
     #[validate(schema(function = "valid_schema_fn2"))]
+    #[allow(dead_code)]
     struct TestStruct {
         val: String,
     }
@@ -47,6 +49,7 @@ fn can_fail_schema_fn_validation() {
 
     #[derive(Debug, Validate)]
     #[validate(schema(function = "invalid_schema_fn"))]
+    #[allow(dead_code)]
     struct TestStruct {
         val: String,
     }
@@ -74,6 +77,7 @@ fn can_fail_multiple_schema_fn_validation() {
     #[derive(Debug, Validate)]
     #[validate(schema(function = "invalid_schema_fn"))]
     #[validate(schema(function = "invalid_schema_fn2"))]
+    #[allow(dead_code)]
     struct TestStruct {
         val: String,
     }
@@ -97,6 +101,7 @@ fn can_specify_message_for_schema_fn() {
 
     #[derive(Debug, Validate)]
     #[validate(schema(function = "invalid_schema_fn", message = "oops"))]
+    #[allow(dead_code)]
     struct TestStruct {
         val: String,
     }
@@ -110,6 +115,27 @@ fn can_specify_message_for_schema_fn() {
     assert_eq!(errs["__all__"][0].clone().message.unwrap(), "oops");
 }
 
+#[test]
+fn can_specify_code_for_schema_fn() {
+    fn invalid_schema_fn(_: &TestStruct) -> Result<(), ValidationError> {
+        Err(ValidationError::new("meh"))
+    }
+
+    #[derive(Debug, Validate)]
+    #[validate(schema(function = "invalid_schema_fn", code = "oops"))]
+    #[allow(dead_code)]
+    struct TestStruct {
+        val: String,
+    }
+    let s = TestStruct { val: String::new() };
+    let res = s.validate();
+    assert!(res.is_err());
+    let err = res.unwrap_err();
+    let errs = err.field_errors();
+    assert_eq!(errs["__all__"].len(), 1);
+    assert_eq!(errs["__all__"][0].code, "oops");
+}
+
 #[test]
 fn can_choose_to_run_schema_validation_even_after_field_errors() {
     fn invalid_schema_fn(_: &TestStruct) -> Result<(), ValidationError> {
@@ -117,6 +143,7 @@ fn can_choose_to_run_schema_validation_even_after_field_errors() {
     }
     #[derive(Debug, Validate)]
     #[validate(schema(function = "invalid_schema_fn", skip_on_field_errors = false))]
+    #[allow(dead_code)]
     struct TestStruct {
         val: String,
         #[validate(range(min = 1, max = 10))]
@@ -136,3 +163,54 @@ fn can_choose_to_run_schema_validation_even_after_field_errors() {
     assert_eq!(errs["num"].len(), 1);
     assert_eq!(errs["num"][0].clone().code, "range");
 }
+
+#[test]
+fn can_return_several_errors_from_schema_fn() {
+    fn validate_all(_: &TestStruct) -> Vec<ValidationError> {
+        vec![ValidationError::new("meh"), ValidationError::new("bleh")]
+    }
+
+    #[derive(Debug, Validate)]
+    #[validate(schema(function = "validate_all", code = "oops"))]
+    #[allow(dead_code)]
+    struct TestStruct {
+        val: String,
+    }
+
+    let err = TestStruct { val: String::new() }.validate().unwrap_err();
+    let errs = err.field_errors();
+    assert_eq!(errs["__all__"].len(), 2);
+    assert!(errs["__all__"].iter().all(|e| e.code == "oops"));
+}
+
+#[test]
+fn can_report_errors_on_fields_from_schema_fn() {
+    fn validate_dates(s: &TestStruct) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+        if s.start > s.end {
+            errors.add("start", ValidationError::new("after_end"));
+            errors.add("end", ValidationError::new("before_start"));
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    #[derive(Debug, Validate)]
+    #[validate(schema(function = "validate_dates", skip_on_field_errors = false))]
+    struct TestStruct {
+        #[validate(range(max = 100))]
+        start: u32,
+        end: u32,
+    }
+
+    assert!(TestStruct { start: 1, end: 2 }.validate().is_ok());
+    let err = TestStruct { start: 200, end: 2 }.validate().unwrap_err();
+    let errs = err.field_errors();
+    assert!(!errs.contains_key("__all__"));
+    let codes: Vec<_> = errs["start"].iter().map(|e| e.code.as_ref()).collect();
+    assert_eq!(codes, vec!["range", "after_end"]);
+    assert_eq!(errs["end"][0].code, "before_start");
+}
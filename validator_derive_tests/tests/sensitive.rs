@@ -0,0 +1,62 @@
+use serde_json::json;
+use validator::{redact_sensitive_value, Validate};
+
+#[derive(Debug, Validate)]
+struct Payment {
+    #[validate(sensitive, credit_card)]
+    card: String,
+    #[validate(sensitive, length(min = 8))]
+    password: String,
+    #[validate(sensitive, must_match = "password")]
+    password2: String,
+    #[validate(length(min = 8))]
+    name: String,
+}
+
+#[test]
+fn sensitive_values_are_redacted() {
+    let payment = Payment {
+        card: "5236313877109141".to_string(),
+        password: "hunter2".to_string(),
+        password2: "hunter3".to_string(),
+        name: "bob".to_string(),
+    };
+    let errors = payment.validate().unwrap_err();
+    let errors = errors.field_errors();
+
+    assert_eq!(errors["card"][0].params["value"], json!("****9141"));
+    assert!(!errors["password"][0].params.contains_key("value"));
+    assert_eq!(errors["password"][0].params["min"], json!(8));
+    assert!(errors["password2"][0].params.is_empty());
+    assert_eq!(errors["name"][0].params["value"], json!("bob"));
+}
+
+#[test]
+fn sensitive_applies_to_each() {
+    #[derive(Debug, Validate)]
+    struct Tokens {
+        #[validate(sensitive, each(length(equal = 4)))]
+        tokens: Vec<String>,
+    }
+
+    let tokens = Tokens { tokens: vec!["secret".to_string()] };
+    let errors = tokens.validate().unwrap_err();
+    let errors = match errors.errors()["tokens"] {
+        validator::ValidationErrorsKind::List(ref list) => {
+            list[&0].field_errors()["__all__"].clone()
+        }
+        ref kind => panic!("Expected a list of errors, got {:?}", kind),
+    };
+    assert!(!errors[0].params.contains_key("value"));
+}
+
+#[test]
+fn default_hook_masks_card_numbers() {
+    assert_eq!(
+        redact_sensitive_value("credit_card", json!("4539571147647251"), true),
+        Some(json!("****7251"))
+    );
+    assert_eq!(redact_sensitive_value("credit_card", json!("123"), true), Some(json!("****")));
+    assert_eq!(redact_sensitive_value("length", json!("secret"), true), None);
+    assert_eq!(redact_sensitive_value("length", json!("bob"), false), Some(json!("bob")));
+}
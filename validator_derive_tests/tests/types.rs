@@ -0,0 +1,106 @@
+use std::borrow::Cow;
+use std::collections::HashSet;
+
+use validator::Validate;
+
+type Email = String;
+type Age = u8;
+
+#[test]
+fn can_validate_fully_qualified_option() {
+    #[derive(Debug, Validate)]
+    struct TestStruct {
+        #[validate(email, length(max = 20))]
+        val: std::option::Option<String>,
+        #[validate(range(max = 10))]
+        num: ::core::option::Option<u32>,
+    }
+
+    let s = TestStruct { val: None, num: None };
+    assert!(s.validate().is_ok());
+
+    let s = TestStruct { val: Some("bob".to_string()), num: Some(11) };
+    let res = s.validate();
+    assert!(res.is_err());
+    let err = res.unwrap_err();
+    let errs = err.field_errors();
+    assert_eq!(errs["val"][0].code, "email");
+    assert_eq!(errs["val"][0].params["value"], "bob");
+    assert_eq!(errs["num"][0].code, "range");
+    assert_eq!(errs["num"][0].params["value"], 11);
+}
+
+#[test]
+fn can_validate_type_aliases() {
+    #[derive(Debug, Validate)]
+    struct TestStruct {
+        #[validate(email)]
+        mail: Email,
+        #[validate(range(min = 18))]
+        age: Age,
+    }
+
+    let s = TestStruct { mail: "bob@bob.com".to_string(), age: 18 };
+    assert!(s.validate().is_ok());
+
+    let s = TestStruct { mail: "bob".to_string(), age: 17 };
+    let res = s.validate();
+    assert!(res.is_err());
+    let err = res.unwrap_err();
+    let errs = err.field_errors();
+    assert_eq!(errs["mail"][0].code, "email");
+    assert_eq!(errs["age"][0].code, "range");
+}
+
+#[test]
+fn can_validate_types_implementing_the_traits() {
+    #[derive(Debug, Validate)]
+    struct TestStruct {
+        #[validate(url, length(max = 20))]
+        site: Option<Box<str>>,
+        #[validate(contains = "he")]
+        name: Cow<'static, str>,
+        #[validate(length(min = 1))]
+        tags: HashSet<String>,
+    }
+
+    let s = TestStruct {
+        site: Some("http://hello.com".into()),
+        name: "hello".into(),
+        tags: vec!["rust".to_string()].into_iter().collect(),
+    };
+    assert!(s.validate().is_ok());
+
+    let s = TestStruct {
+        site: Some("http://a-very-long-domain.com".into()),
+        name: "bob".into(),
+        tags: HashSet::new(),
+    };
+    let res = s.validate();
+    assert!(res.is_err());
+    let err = res.unwrap_err();
+    let errs = err.field_errors();
+    assert_eq!(errs["site"][0].code, "length");
+    assert_eq!(errs["name"][0].code, "contains");
+    assert_eq!(errs["tags"][0].code, "length");
+}
+
+#[test]
+fn can_validate_generic_fields() {
+    #[derive(Debug, Validate)]
+    struct TestStruct<T: AsRef<str>> {
+        #[validate(email)]
+        mail: T,
+    }
+
+    let s = TestStruct { mail: "bob@bob.com" };
+    assert!(s.validate().is_ok());
+
+    let s = TestStruct { mail: Cow::from("bob") };
+    let res = s.validate();
+    assert!(res.is_err());
+    let err = res.unwrap_err();
+    let errs = err.field_errors();
+    assert_eq!(errs["mail"][0].code, "email");
+    assert_eq!(errs["mail"][0].params["value"], "bob");
+}
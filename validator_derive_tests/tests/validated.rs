@@ -0,0 +1,40 @@
+use serde::Deserialize;
+use validator::{Validate, Validated};
+
+#[derive(Debug, Deserialize, Validate)]
+struct SignupData {
+    #[validate(email)]
+    mail: String,
+    #[validate(range(min = 18))]
+    age: u8,
+}
+
+fn register(data: Validated<SignupData>) -> String {
+    format!("{} ({})", data.mail, data.age)
+}
+
+#[test]
+fn can_only_create_validated_from_valid_value() {
+    let data = SignupData { mail: "bob@bob.com".to_string(), age: 20 };
+    let data = Validated::new(data).unwrap();
+    assert_eq!(register(data), "bob@bob.com (20)");
+
+    let data = SignupData { mail: "bob".to_string(), age: 20 };
+    let errs = Validated::new(data).unwrap_err();
+    assert_eq!(errs.field_errors()["mail"][0].code, "email");
+}
+
+#[test]
+fn deserializing_validated_validates() {
+    #[derive(Debug, Deserialize)]
+    struct Request {
+        data: Validated<SignupData>,
+    }
+
+    let req: Request =
+        serde_json::from_str(r#"{"data": {"mail": "bob@bob.com", "age": 20}}"#).unwrap();
+    assert_eq!(req.data.into_inner().age, 20);
+
+    let res = serde_json::from_str::<Request>(r#"{"data": {"mail": "bob@bob.com", "age": 2}}"#);
+    assert!(res.is_err());
+}
@@ -0,0 +1,66 @@
+use serde_json::json;
+use serde_json::Value;
+use validator::{
+    reset_value_hook, set_value_hook, set_value_params, value_params, Validate, ValueParams,
+};
+
+#[derive(Debug, Validate)]
+struct Post {
+    #[validate(length(max = 5))]
+    title: String,
+    #[validate(length(max = 2))]
+    tags: Vec<String>,
+    #[validate(length(max = 5))]
+    subtitle: Option<String>,
+}
+
+fn hide_long_values(_code: &str, value: Value, _sensitive: bool) -> Option<Value> {
+    match value {
+        Value::String(ref s) if s.len() > 10 => Some(Value::from("<hidden>")),
+        value => Some(value),
+    }
+}
+
+// The policy is global so everything is checked in a single test
+#[test]
+fn value_params_follow_the_policy() {
+    let post = Post {
+        title: "A very long title".to_string(),
+        tags: vec!["a".to_string(), "b".to_string(), "a longer tag".to_string()],
+        subtitle: Some("A long subtitle".to_string()),
+    };
+
+    assert_eq!(value_params(), ValueParams::Full);
+    let errors = post.validate().unwrap_err();
+    let errors = errors.field_errors();
+    assert_eq!(errors["title"][0].params["value"], json!("A very long title"));
+    assert_eq!(errors["tags"][0].params["value"], json!(["a", "b", "a longer tag"]));
+
+    set_value_params(ValueParams::Truncate(4));
+    assert_eq!(value_params(), ValueParams::Truncate(4));
+    let errors = post.validate().unwrap_err();
+    let errors = errors.field_errors();
+    assert_eq!(errors["title"][0].params["value"], json!("A ve"));
+    assert_eq!(errors["tags"][0].params["value"], json!(["a", "b", "a lo"]));
+    assert_eq!(errors["subtitle"][0].params["value"], json!("A lo"));
+    assert_eq!(errors["title"][0].params["max"], json!(5));
+
+    set_value_params(ValueParams::Omit);
+    let errors = post.validate().unwrap_err();
+    let errors = errors.field_errors();
+    assert!(!errors["title"][0].params.contains_key("value"));
+    assert_eq!(errors["title"][0].params["max"], json!(5));
+
+    set_value_params(ValueParams::Full);
+    let errors = post.validate().unwrap_err();
+    assert_eq!(errors.field_errors()["title"][0].params["value"], json!("A very long title"));
+
+    set_value_hook(hide_long_values);
+    set_value_params(ValueParams::Truncate(4));
+    let errors = post.validate().unwrap_err();
+    let errors = errors.field_errors();
+    assert_eq!(errors["title"][0].params["value"], json!("<hid"));
+    assert_eq!(errors["tags"][0].params["value"], json!(["a", "b", "a lo"]));
+    set_value_params(ValueParams::Full);
+    reset_value_hook();
+}
@@ -10,6 +10,12 @@ pub enum Validator {
     Custom(String),
     // String is the name of the field to match
     MustMatch(String),
+    // String is the name of the field to compare to
+    GreaterThan(String),
+    GreaterOrEqual(String),
+    LessThan(String),
+    LessOrEqual(String),
+    NotEqual(String),
     // value is a &str or a HashMap<String, ..>
     Contains(String),
     // No implementation in this crate, it's all in validator_derive
@@ -33,6 +39,24 @@ pub enum Validator {
     NonControlCharacter,
     Required,
     RequiredNested,
+    NotBlank,
+    NotEmpty,
+    // The name of the other field and the value it is compared to, as Rust source
+    RequiredIf {
+        other: String,
+        value: String,
+    },
+    RequiredUnless {
+        other: String,
+        value: String,
+    },
+    // String is the name of the other field
+    RequiredWith(String),
+    RequiredWithout(String),
+    // The struct level presence rules, with the names of the fields
+    AtLeastOneOf(Vec<String>),
+    ExactlyOneOf(Vec<String>),
+    MutuallyExclusive(Vec<String>),
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -45,6 +69,11 @@ impl Validator {
     pub fn code(&self) -> &'static str {
         match *self {
             Validator::MustMatch(_) => "must_match",
+            Validator::GreaterThan(_) => "greater_than",
+            Validator::GreaterOrEqual(_) => "greater_or_equal",
+            Validator::LessThan(_) => "less_than",
+            Validator::LessOrEqual(_) => "less_or_equal",
+            Validator::NotEqual(_) => "not_equal",
             Validator::Email => "email",
             Validator::Url => "url",
             Validator::Custom(_) => "custom",
@@ -61,6 +90,15 @@ impl Validator {
             Validator::NonControlCharacter => "non_control_character",
             Validator::Required => "required",
             Validator::RequiredNested => "required_nested",
+            Validator::NotBlank => "not_blank",
+            Validator::NotEmpty => "not_empty",
+            Validator::RequiredIf { .. } => "required_if",
+            Validator::RequiredUnless { .. } => "required_unless",
+            Validator::RequiredWith(_) => "required_with",
+            Validator::RequiredWithout(_) => "required_without",
+            Validator::AtLeastOneOf(_) => "at_least_one_of",
+            Validator::ExactlyOneOf(_) => "exactly_one_of",
+            Validator::MutuallyExclusive(_) => "mutually_exclusive",
         }
     }
 }